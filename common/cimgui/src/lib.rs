@@ -412,6 +412,7 @@ pub enum Dir {
 }
 
 #[repr(i32)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum SortDirection {
     None = sys::ImGuiSortDirection_None,
     Ascending = sys::ImGuiSortDirection_Ascending,
@@ -751,6 +752,21 @@ pub fn same_line(offset_from_start_x: Option<f32>, spacing: Option<f32>) {
     unsafe { sys::igSameLine(offset_from_start_x.unwrap_or(0.0), spacing.unwrap_or(-1.0)) }
 }
 
+pub fn checkbox(label: &str, value: &mut bool) -> Result<bool, NulError> {
+    let label = CString::new(label)?;
+    Ok(unsafe { sys::igCheckbox(label.as_ptr(), value) })
+}
+
+/// Scrolls the current window so that the last item is at `center_y_ratio` of its height
+/// (0.0 for the top, 0.5 by default).
+pub fn set_scroll_here_y(center_y_ratio: Option<f32>) {
+    unsafe { sys::igSetScrollHereY(center_y_ratio.unwrap_or(0.5)) }
+}
+
+pub fn set_next_item_width(item_width: f32) {
+    unsafe { sys::igSetNextItemWidth(item_width) }
+}
+
 pub fn collapsing_header(
     label: &str,
     visible: Option<&mut bool>,
@@ -811,6 +827,51 @@ pub fn small_button(label: &str) -> Result<bool, NulError> {
     Ok(unsafe { sys::igSmallButton(label.as_ptr()) })
 }
 
+/// `text` is copied into a buffer of at least `capacity` bytes for editing, and updated
+/// with its contents afterwards.
+pub fn input_text(
+    label: &str,
+    text: &mut String,
+    capacity: usize,
+    flags: Option<InputTextFlags>,
+) -> Result<bool, NulError> {
+    let label = CString::new(label)?;
+    let mut buf = text.clone().into_bytes();
+    buf.resize(capacity.max(buf.len() + 1), 0);
+    let ret = unsafe {
+        sys::igInputText(
+            label.as_ptr(),
+            buf.as_mut_ptr() as *mut _,
+            buf.len() as _,
+            flags.unwrap_or(InputTextFlags::None) as sys::ImGuiInputTextFlags,
+            None,
+            std::ptr::null_mut(),
+        )
+    };
+    let len = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    *text = String::from_utf8_lossy(&buf[..len]).into_owned();
+    Ok(ret)
+}
+
+pub fn input_int(
+    label: &str,
+    value: &mut i32,
+    step: Option<i32>,
+    step_fast: Option<i32>,
+    flags: Option<InputTextFlags>,
+) -> Result<bool, NulError> {
+    let label = CString::new(label)?;
+    Ok(unsafe {
+        sys::igInputInt(
+            label.as_ptr(),
+            value,
+            step.unwrap_or(1),
+            step_fast.unwrap_or(100),
+            flags.unwrap_or(InputTextFlags::None) as sys::ImGuiInputTextFlags,
+        )
+    })
+}
+
 pub fn image(
     user_texture_id: TextureID,
     size: Vec2,
@@ -899,6 +960,53 @@ pub fn table_setup_column(
     Ok(())
 }
 
+/// The column the current table is sorted by and its direction, if it's sortable and
+/// sorted. Only the primary sort is returned.
+pub fn table_get_sort_specs() -> Option<(i32, SortDirection)> {
+    unsafe {
+        let specs = sys::igTableGetSortSpecs().as_ref()?;
+        if specs.SpecsCount == 0 {
+            return None;
+        }
+        let spec = &*specs.Specs;
+        let direction = if spec.SortDirection() as i32 == SortDirection::Descending as i32 {
+            SortDirection::Descending
+        } else {
+            SortDirection::Ascending
+        };
+        Some((spec.ColumnIndex as i32, direction))
+    }
+}
+
+pub fn plot_lines(
+    label: &str,
+    values: &[f32],
+    overlay_text: Option<&str>,
+    scale_min: Option<f32>,
+    scale_max: Option<f32>,
+    graph_size: Option<Vec2>,
+) -> Result<(), NulError> {
+    let label = CString::new(label)?;
+    let overlay_text = overlay_text.map(CString::new).transpose()?;
+    unsafe {
+        sys::igPlotLines_FloatPtr(
+            label.as_ptr(),
+            values.as_ptr(),
+            values.len() as i32,
+            0,
+            overlay_text
+                .as_ref()
+                .map(|t| t.as_ptr())
+                .unwrap_or(std::ptr::null()),
+            scale_min.unwrap_or(f32::MAX),
+            scale_max.unwrap_or(f32::MAX),
+            graph_size.unwrap_or(Vec2::ZERO),
+            std::mem::size_of::<f32>() as i32,
+        );
+    }
+    Ok(())
+}
+
 pub fn begin_tab_bar(str_id: &str, flags: Option<TabBarFlags>) -> Result<bool, NulError> {
     let str_id = CString::new(str_id)?;
     unsafe {
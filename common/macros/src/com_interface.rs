@@ -0,0 +1,310 @@
+use quote::{format_ident, quote};
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    braced, Attribute, Error, Expr, FnArg, Ident, Pat, Path, Result, Signature, Token, Type,
+};
+
+mod kw {
+    syn::custom_keyword!(payload);
+    syn::custom_keyword!(callback);
+    syn::custom_keyword!(stats);
+    syn::custom_keyword!(methods);
+}
+
+/// `#[capture(name: Type = expr)]`: an extra value to store in the payload, evaluated
+/// after the original method has been called.
+struct Capture {
+    name: Ident,
+    type_expr: Type,
+    value: Expr,
+}
+impl Parse for Capture {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let type_expr: Type = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Expr = input.parse()?;
+
+        Ok(Capture {
+            name,
+            type_expr,
+            value,
+        })
+    }
+}
+
+struct Method {
+    captures: Vec<Capture>,
+    signature: Signature,
+    arguments: Vec<(Ident, Type)>,
+}
+impl Parse for Method {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let captures = input
+            .call(Attribute::parse_outer)?
+            .iter()
+            .map(|attribute| {
+                if attribute.path.is_ident("capture") {
+                    attribute.parse_args::<Capture>()
+                } else {
+                    Err(Error::new_spanned(attribute, "expected #[capture(...)]"))
+                }
+            })
+            .collect::<Result<_>>()?;
+        let signature: Signature = input.parse()?;
+
+        // `This` is implicit.
+        let arguments = signature
+            .inputs
+            .iter()
+            .map(|argument| match argument {
+                FnArg::Typed(argument) => match argument.pat.as_ref() {
+                    Pat::Ident(name) => Ok((name.ident.clone(), (*argument.ty).clone())),
+                    pat => Err(Error::new_spanned(pat, "expected an argument name")),
+                },
+                FnArg::Receiver(receiver) => {
+                    Err(Error::new_spanned(receiver, "`This` is passed implicitly"))
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Method {
+            captures,
+            signature,
+            arguments,
+        })
+    }
+}
+
+enum Property {
+    Payload(Ident),
+    Callback(Path),
+    Stats(Path),
+    Methods(Punctuated<Method, Token![;]>),
+}
+impl Parse for Property {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::payload) {
+            input.parse::<kw::payload>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Property::Payload(input.parse()?))
+        } else if lookahead.peek(kw::callback) {
+            input.parse::<kw::callback>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Property::Callback(input.parse()?))
+        } else if lookahead.peek(kw::stats) {
+            input.parse::<kw::stats>()?;
+            input.parse::<Token![:]>()?;
+            Ok(Property::Stats(input.parse()?))
+        } else if lookahead.peek(kw::methods) {
+            input.parse::<kw::methods>()?;
+            input.parse::<Token![:]>()?;
+
+            let content;
+            braced!(content in input);
+
+            Ok(Property::Methods(content.parse_terminated(Method::parse)?))
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+pub struct ComInterface {
+    name: Ident,
+    payload: Ident,
+    callback: Path,
+    stats: Option<Path>,
+    methods: Vec<Method>,
+}
+impl Parse for ComInterface {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![,]>()?;
+
+        let content;
+        braced!(content in input);
+
+        let properties: Punctuated<Property, Token![,]> =
+            content.parse_terminated(Property::parse)?;
+
+        let (mut payload, mut callback, mut stats, mut methods) = (None, None, None, vec![]);
+        for property in properties {
+            match property {
+                Property::Payload(p) => payload = Some(p),
+                Property::Callback(c) => callback = Some(c),
+                Property::Stats(s) => stats = Some(s),
+                Property::Methods(ms) => methods = ms.into_iter().collect(),
+            }
+        }
+
+        Ok(ComInterface {
+            payload: payload.ok_or_else(|| Error::new(name.span(), "missing `payload`"))?,
+            callback: callback.ok_or_else(|| Error::new(name.span(), "missing `callback`"))?,
+            stats,
+            name,
+            methods,
+        })
+    }
+}
+
+pub fn generate(interface: ComInterface) -> proc_macro2::TokenStream {
+    let ComInterface {
+        name,
+        payload,
+        callback,
+        stats,
+        methods,
+    } = interface;
+    let vtbl = format_ident!("{}Vtbl", name);
+    let hooks_mod = format_ident!("{}_hooks", name);
+
+    let method_names: Vec<_> = methods.iter().map(|m| &m.signature.ident).collect();
+    let method_count = methods.len();
+    let method_indices = 0..method_count;
+
+    let vtbl_fields = methods.iter().map(|m| {
+        let name = &m.signature.ident;
+        let types = m.arguments.iter().map(|(_, t)| t);
+        let output = &m.signature.output;
+        quote! {
+            pub #name: unsafe extern "system" fn(*mut ::std::os::raw::c_void, #(#types),*) #output
+        }
+    });
+
+    let variants = methods.iter().map(|m| {
+        let name = &m.signature.ident;
+        let types = m.arguments.iter().map(|(_, t)| t);
+        let capture_types = m.captures.iter().map(|c| &c.type_expr);
+        quote! { #name(#(#types,)* #(#capture_types,)*) }
+    });
+
+    let arguments_arms = methods.iter().map(|m| {
+        let name = &m.signature.ident;
+        let names: Vec<_> = m.arguments.iter().map(|(n, _)| n).collect();
+        quote! {
+            Self::#name(#(#names,)* ..) => vec![#((stringify!(#names), format!("{:?}", #names)),)*]
+        }
+    });
+
+    let captures_arms = methods.iter().map(|m| {
+        let name = &m.signature.ident;
+        let skipped = m.arguments.iter().map(|_| quote! { _ });
+        let names: Vec<_> = m.captures.iter().map(|c| &c.name).collect();
+        quote! {
+            Self::#name(#(#skipped,)* #(#names,)*) => vec![#((stringify!(#names), format!("{:?}", #names)),)*]
+        }
+    });
+
+    let hooks = methods.iter().map(|m| {
+        let name = &m.signature.ident;
+        let output = &m.signature.output;
+        let (names, types): (Vec<_>, Vec<_>) = m.arguments.iter().map(|(n, t)| (n, t)).unzip();
+        let capture_names: Vec<_> = m.captures.iter().map(|c| &c.name).collect();
+        let capture_types = m.captures.iter().map(|c| &c.type_expr);
+        let capture_values = m.captures.iter().map(|c| &c.value);
+
+        let call = quote! { ((*super::ORIGINAL_VTABLE.unwrap()).#name)(This, #(#names),*) };
+        let call = match &stats {
+            Some(stats) => quote! { #stats.original(|| #call) },
+            None => call,
+        };
+        let (call, ret) = match output {
+            syn::ReturnType::Default => (quote! { #call; }, quote! {}),
+            _ => (quote! { let ret = #call; }, quote! { ret }),
+        };
+        let body = quote! {
+            #call
+            #(let #capture_names: #capture_types = #capture_values;)*
+            #callback(#payload::#name(#(#names,)* #(#capture_names,)*));
+            #ret
+        };
+        // The whole body goes through `call`, so that the hook counts as active for as
+        // long as possible.
+        let body = match &stats {
+            Some(stats) => quote! { #stats.call(|| { #body }) },
+            None => body,
+        };
+
+        quote! {
+            pub unsafe extern "system" fn #name(
+                This: *mut ::std::os::raw::c_void,
+                #(#names: #types),*
+            ) #output {
+                #body
+            }
+        }
+    });
+
+    quote! {
+        #[repr(C)]
+        #[allow(non_snake_case, dead_code)]
+        pub struct #vtbl {
+            #(#vtbl_fields,)*
+        }
+
+        #[repr(C)]
+        #[allow(dead_code)]
+        pub struct #name {
+            pub vtbl: *const #vtbl,
+        }
+
+        /// A call to a method of the interface, with its arguments and any captures.
+        #[derive(Clone)]
+        #[allow(dead_code)]
+        pub enum #payload {
+            #(#variants,)*
+        }
+
+        #[allow(dead_code)]
+        impl #payload {
+            pub const COUNT: usize = #method_count;
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#method_names(..) => stringify!(#method_names),)*
+                }
+            }
+
+            /// The index of the method in the vtable.
+            pub fn index(&self) -> usize {
+                match self {
+                    #(Self::#method_names(..) => #method_indices,)*
+                }
+            }
+
+            /// The name and value of each argument, not including captures.
+            #[allow(non_snake_case)]
+            pub fn arguments(&self) -> Vec<(&'static str, String)> {
+                match self {
+                    #(#arguments_arms,)*
+                }
+            }
+
+            /// The name and value of each capture.
+            pub fn captures(&self) -> Vec<(&'static str, String)> {
+                match self {
+                    #(#captures_arms,)*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        static mut ORIGINAL_VTABLE: Option<*const #vtbl> = None;
+        #[allow(dead_code)]
+        static HOOKED_VTABLE: #vtbl = #vtbl {
+            #(#method_names: #hooks_mod::#method_names,)*
+        };
+
+        #[allow(non_snake_case)]
+        mod #hooks_mod {
+            use super::*;
+
+            #(#hooks)*
+        }
+    }
+}
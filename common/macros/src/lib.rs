@@ -1,11 +1,13 @@
+mod com_interface;
+
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::{
-    braced, bracketed, parenthesized, parse_macro_input, Expr, Ident, LitInt, LitStr, Result,
-    Signature, Token, Type,
+    braced, bracketed, parenthesized, parse_macro_input, Expr, Ident, LitInt, Result, Signature,
+    Token, Type,
 };
 
 // will print the AST every time a game class is instantiated with the set prefix.
@@ -45,7 +47,7 @@ impl Parse for Field {
 }
 
 enum LocationType {
-    Signature(String),
+    Signature(Expr),
 }
 
 struct Function {
@@ -61,7 +63,7 @@ impl Parse for Function {
 
         let signature_contents;
         parenthesized!(signature_contents in content);
-        let code_signature = signature_contents.parse::<LitStr>()?.value();
+        let code_signature = signature_contents.parse::<Expr>()?;
         let signature = input.parse::<Signature>()?;
 
         Ok(Function {
@@ -73,7 +75,7 @@ impl Parse for Function {
 
 enum Property {
     Size(LitInt),
-    Location(Expr),
+    Location(Box<Expr>),
     Fields(Punctuated<Field, Token![,]>),
     Attributes(Vec<syn::Attribute>),
     Functions(Punctuated<Function, Token![;]>),
@@ -161,7 +163,7 @@ fn retrieve_properties(
                 size = Some(v.base10_parse()?);
             }
             Property::Location(e) => {
-                location = Some(e);
+                location = Some(*e);
             }
             Property::Fields(fs) => {
                 fields = fs
@@ -225,7 +227,7 @@ fn generate_getters(fields: &[(u32, Ident, Type)]) -> Vec<proc_macro2::TokenStre
             let name_ptr = format_ident!("{}_ptr", name);
             let name_ptr_mut = format_ident!("{}_ptr_mut", name);
 
-            let ref_methods = if offset % 4 == 0 {
+            let ref_methods = if offset.is_multiple_of(4) {
                 let name = format_ident!("{}", name);
                 let name_mut = format_ident!("{}_mut", name);
 
@@ -300,7 +302,7 @@ fn generate_functions(
                     static mut ADDRESS: *mut u8 = ::std::ptr::null_mut();
                     if ADDRESS.is_null() {
                         let module = crate::util::game_module_mut().unwrap();
-                        ADDRESS = module.scan(#code_signature).unwrap();
+                        ADDRESS = module.resolve(#code_signature).unwrap();
                     }
 
                     type FunctionType = extern "system" fn(#(#args_type), *) #output;
@@ -353,3 +355,33 @@ pub fn game_class(item: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Generates everything needed to hook every method of a COM interface by swapping an
+/// object's vtable pointer: the vtable struct, a payload enum with a variant for each
+/// method, `HOOKED_VTABLE` whose entries forward to `ORIGINAL_VTABLE` and then pass the
+/// payload to `callback`, and `ORIGINAL_VTABLE` itself, which must be set before
+/// `HOOKED_VTABLE` is used. As those names are fixed, each interface needs its own module.
+///
+/// ```ignore
+/// com_interface!(ID3D11DeviceContext, {
+///     payload: D3DPayload,
+///     callback: push_back_payload,
+///     methods: {
+///         fn Draw(VertexCount: u32, StartVertexLocation: u32);
+///         #[capture(resources: Vec<ID3D11Resource> = resources_of(NumViews, ppRenderTargetViews))]
+///         fn OMSetRenderTargets(NumViews: u32, ppRenderTargetViews: *mut *const c_void, pDepthStencilView: *mut c_void);
+///     },
+/// });
+/// ```
+///
+/// Methods are listed in vtable order, including those of base interfaces, without `This`.
+/// Captures are evaluated after the original method returns, and are stored in the payload
+/// after the arguments; the payload's `arguments()` and `captures()` list them separately.
+///
+/// If `stats: STATS` is given, each hook runs its whole body through `STATS.call(|| ...)`,
+/// and calls the original method through `STATS.original(|| ...)`.
+#[proc_macro]
+pub fn com_interface(item: TokenStream) -> TokenStream {
+    let interface = parse_macro_input!(item as com_interface::ComInterface);
+    TokenStream::from(com_interface::generate(interface))
+}
@@ -0,0 +1,109 @@
+#![allow(non_snake_case)]
+
+use macros::com_interface;
+
+use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+com_interface!(ICalculator, {
+    payload: CalculatorPayload,
+    callback: record,
+    stats: STATS,
+    methods: {
+        fn Add(a: u32, b: u32) -> u32;
+        #[capture(doubled: u32 = value * 2)]
+        fn Store(value: u32);
+        fn Reset();
+    },
+});
+
+static CALLS: Mutex<Vec<CalculatorPayload>> = Mutex::new(Vec::new());
+static STORED: Mutex<Option<u32>> = Mutex::new(None);
+
+fn record(payload: CalculatorPayload) {
+    // The callback is part of the hook's body, so it runs while the call is active.
+    assert_eq!(STATS.active.load(Ordering::SeqCst), 1);
+    CALLS.lock().unwrap().push(payload);
+}
+
+struct Stats {
+    entered: AtomicUsize,
+    active: AtomicUsize,
+    originals: AtomicUsize,
+}
+
+impl Stats {
+    fn call<T>(&'static self, handler: impl FnOnce() -> T) -> T {
+        self.entered.fetch_add(1, Ordering::SeqCst);
+        self.active.fetch_add(1, Ordering::SeqCst);
+        let ret = handler();
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        ret
+    }
+
+    fn original<T>(&self, original: impl FnOnce() -> T) -> T {
+        self.originals.fetch_add(1, Ordering::SeqCst);
+        original()
+    }
+}
+
+static STATS: Stats = Stats {
+    entered: AtomicUsize::new(0),
+    active: AtomicUsize::new(0),
+    originals: AtomicUsize::new(0),
+};
+
+unsafe extern "system" fn add(_this: *mut c_void, a: u32, b: u32) -> u32 {
+    a + b
+}
+
+unsafe extern "system" fn store(_this: *mut c_void, value: u32) {
+    *STORED.lock().unwrap() = Some(value);
+}
+
+unsafe extern "system" fn reset(_this: *mut c_void) {
+    *STORED.lock().unwrap() = None;
+}
+
+static CALCULATOR_VTABLE: ICalculatorVtbl = ICalculatorVtbl {
+    Add: add,
+    Store: store,
+    Reset: reset,
+};
+
+#[test]
+fn hooked_vtable_forwards_calls_and_records_payloads() {
+    unsafe { ORIGINAL_VTABLE = Some(&CALCULATOR_VTABLE) };
+    let mut calculator = ICalculator {
+        vtbl: &HOOKED_VTABLE,
+    };
+    let this = &mut calculator as *mut ICalculator as *mut c_void;
+
+    unsafe {
+        assert_eq!(((*calculator.vtbl).Add)(this, 2, 3), 5);
+        ((*calculator.vtbl).Store)(this, 7);
+        assert_eq!(*STORED.lock().unwrap(), Some(7));
+        ((*calculator.vtbl).Reset)(this);
+        assert_eq!(*STORED.lock().unwrap(), None);
+    }
+
+    let calls = CALLS.lock().unwrap();
+    let names: Vec<_> = calls.iter().map(|c| (c.name(), c.index())).collect();
+    assert_eq!(names, vec![("Add", 0), ("Store", 1), ("Reset", 2)]);
+
+    assert_eq!(
+        calls[0].arguments(),
+        vec![("a", "2".to_string()), ("b", "3".to_string())]
+    );
+    // Captures are stored after the arguments, and listed separately.
+    assert!(matches!(calls[1], CalculatorPayload::Store(7, 14)));
+    assert_eq!(calls[1].arguments(), vec![("value", "7".to_string())]);
+    assert_eq!(calls[1].captures(), vec![("doubled", "14".to_string())]);
+    assert!(calls[0].captures().is_empty());
+    assert!(calls[2].arguments().is_empty());
+    assert_eq!(CalculatorPayload::COUNT, 3);
+
+    assert_eq!(STATS.entered.load(Ordering::SeqCst), 3);
+    assert_eq!(STATS.originals.load(Ordering::SeqCst), 3);
+}
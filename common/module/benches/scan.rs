@@ -0,0 +1,66 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use module::Pattern;
+
+const IMAGE_SIZE: usize = 32 * 1024 * 1024;
+const PATTERN_COUNT: usize = 32;
+
+// xorshift, so that the image is the same between runs
+struct Rng(u64);
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Builds an image of random bytes, and patterns taken from random locations within
+/// it with some of their bytes wildcarded.
+fn synthetic_image() -> (Vec<u8>, Vec<Pattern>) {
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    let image: Vec<u8> = (0..IMAGE_SIZE).map(|_| rng.next() as u8).collect();
+
+    let patterns = (0..PATTERN_COUNT)
+        .map(|_| {
+            let len = 12 + (rng.next() % 16) as usize;
+            let location = (rng.next() as usize) % (IMAGE_SIZE - len);
+            let pattern = image[location..location + len]
+                .iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    if i > 0 && rng.next().is_multiple_of(4) {
+                        "?".to_string()
+                    } else {
+                        format!("{:02X}", b)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            Pattern::parse(&pattern).unwrap()
+        })
+        .collect();
+
+    (image, patterns)
+}
+
+fn scan(c: &mut Criterion) {
+    let (image, patterns) = synthetic_image();
+    assert_eq!(
+        patterns.iter().map(|p| p.scan(&image)).collect::<Vec<_>>(),
+        Pattern::scan_many(&patterns, &image)
+    );
+
+    let mut group = c.benchmark_group("scan");
+    group.sample_size(10);
+    group.bench_function("individual", |b| {
+        b.iter(|| patterns.iter().map(|p| p.scan(&image)).collect::<Vec<_>>())
+    });
+    group.bench_function("batched", |b| {
+        b.iter(|| Pattern::scan_many(&patterns, &image))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan);
+criterion_main!(benches);
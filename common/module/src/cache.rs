@@ -0,0 +1,70 @@
+use crate::RelativeOperand;
+
+use serde::{Deserialize, Serialize};
+
+/// Bump this whenever the format of `SerializedCache` changes.
+pub const CACHE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CacheKey {
+    Regular(String),
+    RelativeCallsite(String),
+    AfterPtr(String, usize),
+    Relative(String, RelativeOperand),
+}
+
+impl CacheKey {
+    pub fn pattern(&self) -> &str {
+        match self {
+            CacheKey::Regular(pattern) => pattern,
+            CacheKey::RelativeCallsite(pattern) => pattern,
+            CacheKey::AfterPtr(pattern, _) => pattern,
+            CacheKey::Relative(pattern, _) => pattern,
+        }
+    }
+
+    /// The operand that has to be followed from the match to get to the cached location, if any.
+    pub fn operand(&self) -> Option<RelativeOperand> {
+        match self {
+            CacheKey::Regular(_) | CacheKey::AfterPtr(_, _) => None,
+            CacheKey::RelativeCallsite(_) => Some(RelativeOperand::CALL),
+            CacheKey::Relative(_, operand) => Some(*operand),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Where the pattern matched; used to verify the entry when loading the cache.
+    pub match_offset: usize,
+    pub offset: usize,
+}
+
+/// Identifies a specific build of an executable. Taken from the PE headers, so that
+/// it's cheap to compute and doesn't depend on the compiler used to build us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageIdentity {
+    pub timestamp: u32,
+    pub checksum: u32,
+    pub size_of_image: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SerializedCache {
+    pub version: u32,
+    pub identity: ImageIdentity,
+    pub entries: Vec<(CacheKey, CacheEntry)>,
+}
+
+/// The original cache format, which was keyed on a hash of the executable.
+#[derive(Deserialize)]
+pub struct LegacySerializedCache {
+    pub entries: Vec<(CacheKey, usize)>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum AnySerializedCache {
+    Current(SerializedCache),
+    Legacy(LegacySerializedCache),
+}
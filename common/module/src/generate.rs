@@ -0,0 +1,128 @@
+use crate::{Module, Pattern};
+
+use anyhow::anyhow;
+use iced_x86::{Decoder, DecoderOptions, FlowControl};
+
+// Give up if nothing shorter than this is unique.
+const MAX_SIGNATURE_LENGTH: usize = 128;
+
+impl Module {
+    /// Generates the shortest signature, in whole instructions, that uniquely matches
+    /// the code at `offset` (usually the start of a function). Bytes that will differ
+    /// between builds or loads - relocated addresses, RIP-relative displacements and
+    /// `rel32` branch targets - are wildcarded.
+    pub fn generate_signature(&self, offset: usize) -> anyhow::Result<String> {
+        let bytes = self.as_bytes();
+        let relocations = self.headers()?.relocations(bytes)?;
+        let (code_offset, code) = self.code();
+        if offset < code_offset || offset >= code_offset + code.len() {
+            return Err(anyhow!("{:X} is outside of the code section", offset));
+        }
+
+        let end = (offset + MAX_SIGNATURE_LENGTH).min(code_offset + code.len());
+        let mut decoder = Decoder::with_ip(64, &bytes[offset..end], 0, DecoderOptions::NONE);
+
+        let mut signature: Vec<Option<u8>> = vec![];
+        // Offsets (within the code section) that match the signature so far.
+        let mut candidates: Option<Vec<usize>> = None;
+        while decoder.can_decode() {
+            let instruction = decoder.decode();
+            if instruction.is_invalid() {
+                break;
+            }
+
+            let start = signature.len();
+            let mut instruction_bytes: Vec<_> = bytes
+                [offset + start..offset + start + instruction.len()]
+                .iter()
+                .map(|b| Some(*b))
+                .collect();
+
+            let offsets = decoder.get_constant_offsets(&instruction);
+            let mut wildcard = |at: usize, size: usize| {
+                for b in &mut instruction_bytes[at..at + size] {
+                    *b = None;
+                }
+            };
+            if instruction.is_ip_rel_memory_operand() && offsets.has_displacement() {
+                wildcard(offsets.displacement_offset(), offsets.displacement_size());
+            }
+            if (instruction.is_call_near()
+                || instruction.is_jmp_near()
+                || instruction.is_jcc_near())
+                && offsets.has_immediate()
+            {
+                wildcard(offsets.immediate_offset(), offsets.immediate_size());
+            }
+            for relocation in relocations
+                .iter()
+                .filter(|r| (offset + start..offset + start + instruction.len()).contains(*r))
+            {
+                let at = relocation - (offset + start);
+                wildcard(at, 8.min(instruction.len() - at));
+            }
+            signature.extend(instruction_bytes);
+
+            let pattern = match Pattern::from_bytes(signature.clone()) {
+                Ok(pattern) => pattern,
+                // Nothing but wildcards so far
+                Err(_) => continue,
+            };
+            let remaining: Vec<usize> = match candidates {
+                Some(candidates) => candidates
+                    .into_iter()
+                    .filter(|c| pattern.matches(&code[*c..]))
+                    .collect(),
+                None => pattern.scan(code),
+            };
+
+            if remaining == [offset - code_offset] {
+                return self.verify_generated_signature(offset, pattern);
+            }
+            candidates = Some(remaining);
+
+            if instruction.flow_control() == FlowControl::Return {
+                break;
+            }
+        }
+
+        Err(anyhow!(
+            "failed to find a unique signature for {:X} within {} bytes",
+            offset,
+            signature.len()
+        ))
+    }
+
+    /// Generates a signature for the start of the function containing `offset`, such as
+    /// a return address from a callstack, which is usually in the middle of a function.
+    /// Falls back to `offset` itself if the function's bounds aren't known. Returns where
+    /// the signature matches, along with the signature.
+    pub fn generate_function_signature(&self, offset: usize) -> anyhow::Result<(usize, String)> {
+        let function = self
+            .function_bounds(offset)?
+            .map_or(offset, |bounds| bounds.start);
+        Ok((function, self.generate_signature(function)?))
+    }
+
+    fn verify_generated_signature(
+        &self,
+        offset: usize,
+        pattern: Pattern,
+    ) -> anyhow::Result<String> {
+        // Trailing wildcards don't narrow down the match, so they can go.
+        let mut pattern = pattern.to_string();
+        while let Some(trimmed) = pattern.strip_suffix(" ?") {
+            pattern = trimmed.to_string();
+        }
+
+        match self.scan_all(&pattern)?.as_slice() {
+            [found] if *found == offset => Ok(pattern),
+            found => Err(anyhow!(
+                "generated signature `{}` for {:X} is not unique: {:X?}",
+                pattern,
+                offset,
+                found
+            )),
+        }
+    }
+}
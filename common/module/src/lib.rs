@@ -0,0 +1,605 @@
+mod cache;
+mod generate;
+mod pattern;
+pub mod pe;
+mod recovery;
+pub mod rtti;
+mod signature;
+
+pub use cache::ImageIdentity;
+pub use pattern::*;
+pub use recovery::*;
+pub use signature::*;
+
+use std::collections;
+use std::convert::TryInto;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+#[cfg(windows)]
+use std::{ffi::OsString, mem, os::windows::ffi::OsStringExt};
+
+#[cfg(windows)]
+use windows::Win32::Foundation::{HINSTANCE, PWSTR};
+#[cfg(windows)]
+use windows::Win32::System::LibraryLoader::GetModuleFileNameW;
+#[cfg(windows)]
+use windows::Win32::System::ProcessStatus::{
+    K32EnumProcessModules, K32GetModuleInformation, MODULEINFO,
+};
+#[cfg(windows)]
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+use anyhow::anyhow;
+use once_cell::sync::OnceCell;
+
+use cache::*;
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    #[cfg(windows)]
+    handle: HINSTANCE,
+    path: Option<String>,
+    pub base: *mut u8,
+    _entry_point: *mut u8,
+    image_size: u32,
+    image_backup: Vec<u8>,
+    cache: collections::HashMap<CacheKey, CacheEntry>,
+    // Cached entries that no longer match, e.g. because they're for a previous build.
+    stale_cache: collections::HashMap<CacheKey, CacheEntry>,
+    cache_path: Option<PathBuf>,
+    strict: bool,
+    // Parsed on first use, as `.pdata` doesn't change.
+    function_table: OnceCell<pe::FunctionTable>,
+}
+
+impl Module {
+    #[cfg(windows)]
+    pub fn from_handle(handle: HINSTANCE) -> Module {
+        let mut mod_info = unsafe { std::mem::zeroed() };
+        unsafe {
+            K32GetModuleInformation(
+                GetCurrentProcess(),
+                handle,
+                &mut mod_info,
+                mem::size_of::<MODULEINFO>() as u32,
+            );
+        }
+        Module {
+            handle,
+            path: {
+                let mut buf = [0u16; 1024];
+                let size = unsafe {
+                    GetModuleFileNameW(handle, PWSTR(buf.as_mut_ptr()), buf.len() as u32)
+                } as usize;
+                let os = OsString::from_wide(&buf[0..size]);
+                os.into_string().ok()
+            },
+            base: mod_info.lpBaseOfDll as *mut u8,
+            _entry_point: mod_info.EntryPoint as *mut u8,
+            image_size: mod_info.SizeOfImage,
+            image_backup: vec![],
+            cache: collections::HashMap::new(),
+            stale_cache: collections::HashMap::new(),
+            cache_path: None,
+            strict: false,
+            function_table: OnceCell::new(),
+        }
+    }
+
+    /// Maps an executable from disk, laying out its sections at their virtual addresses
+    /// so that offsets match those of the loaded image. The base is the image's preferred
+    /// base address; as nothing is actually loaded there, it must never be dereferenced.
+    pub fn from_file(path: &Path) -> anyhow::Result<Module> {
+        let file = std::fs::read(path)?;
+        let headers = pe::Headers::parse(&file)?;
+        let image = headers.map_image(&file)?;
+
+        Ok(Module {
+            #[cfg(windows)]
+            handle: HINSTANCE::default(),
+            path: path.to_str().map(|s| s.to_string()),
+            base: headers.image_base as *mut u8,
+            _entry_point: (headers.image_base + headers.entry_point as u64) as *mut u8,
+            image_size: headers.size_of_image,
+            image_backup: image,
+            cache: collections::HashMap::new(),
+            stale_cache: collections::HashMap::new(),
+            cache_path: None,
+            strict: false,
+            function_table: OnceCell::new(),
+        })
+    }
+
+    #[cfg(windows)]
+    pub fn get_all() -> Vec<Module> {
+        let process = unsafe { GetCurrentProcess() };
+        let hinstance_size = mem::size_of::<HINSTANCE>() as u32;
+        let mut temp = HINSTANCE::default();
+        let mut needed = 0u32;
+        unsafe {
+            K32EnumProcessModules(process, &mut temp, hinstance_size, &mut needed);
+        }
+        let mut buf = vec![HINSTANCE::default(); (needed / hinstance_size) as usize];
+        unsafe {
+            K32EnumProcessModules(
+                process,
+                buf.as_mut_ptr(),
+                hinstance_size * (buf.len() as u32),
+                &mut needed,
+            );
+        }
+        buf.into_iter().map(Module::from_handle).collect()
+    }
+
+    pub fn as_bytes_from_memory(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.base as *const u8, self.image_size as usize) }
+    }
+
+    pub fn backup_image(&mut self) {
+        self.image_backup = self.as_bytes_from_memory().to_vec();
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        if self.image_backup.is_empty() {
+            self.as_bytes_from_memory()
+        } else {
+            &self.image_backup
+        }
+    }
+
+    pub fn headers(&self) -> anyhow::Result<pe::Headers> {
+        pe::Headers::parse(self.as_bytes())
+    }
+
+    pub fn identity(&self) -> anyhow::Result<ImageIdentity> {
+        let headers = self.headers()?;
+        Ok(ImageIdentity {
+            timestamp: headers.timestamp,
+            checksum: headers.checksum,
+            size_of_image: headers.size_of_image,
+        })
+    }
+
+    /// When strict, a signature that matches more than once is an error instead of
+    /// resolving to its first match.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns the offset and contents of the code section, which is where signatures
+    /// are searched for. Falls back to the entire image if there's no `.text` section.
+    fn code(&self) -> (usize, &[u8]) {
+        let bytes = self.as_bytes();
+        self.section(".text")
+            .and_then(|range| Some((range.start, bytes.get(range)?)))
+            .unwrap_or((0, bytes))
+    }
+
+    /// Returns the range of the image covered by the section called `name`.
+    pub fn section(&self, name: &str) -> Option<Range<usize>> {
+        self.headers().ok()?.section(name).map(pe::Section::range)
+    }
+
+    /// Returns the offsets of every match of `pattern` in the section called `name`.
+    pub fn scan_section(&self, name: &str, pattern: &str) -> anyhow::Result<Vec<usize>> {
+        let range = self
+            .section(name)
+            .ok_or_else(|| anyhow!("no section called {}", name))?;
+        let bytes = self
+            .as_bytes()
+            .get(range.clone())
+            .ok_or_else(|| anyhow!("section {} is outside the image", name))?;
+        Ok(Pattern::parse(pattern)?
+            .scan(bytes)
+            .into_iter()
+            .map(|offset| range.start + offset)
+            .collect())
+    }
+
+    /// Returns the functions in `.pdata`, which are only read from the image the first time.
+    pub fn function_table(&self) -> anyhow::Result<&pe::FunctionTable> {
+        self.function_table
+            .get_or_try_init(|| self.headers()?.function_table(self.as_bytes()))
+    }
+
+    /// Returns the bounds of the function containing `offset`, according to `.pdata`.
+    /// Functions that have been split into chunks resolve to their main chunk.
+    pub fn function_bounds(&self, offset: usize) -> anyhow::Result<Option<Range<usize>>> {
+        Ok(self.function_table()?.bounds(offset))
+    }
+
+    /// Returns the offsets of every match of `pattern` in the code section.
+    pub fn scan_all(&self, pattern: &str) -> anyhow::Result<Vec<usize>> {
+        let (code_offset, code) = self.code();
+        Ok(Pattern::parse(pattern)?
+            .scan(code)
+            .into_iter()
+            .map(|offset| code_offset + offset)
+            .collect())
+    }
+
+    fn scan_unique(&self, pattern: &str) -> anyhow::Result<usize> {
+        if !self.strict {
+            let (code_offset, code) = self.code();
+            return Pattern::parse(pattern)?
+                .scan_first(code)
+                .map(|offset| code_offset + offset)
+                .ok_or_else(|| anyhow!("failed to scan"));
+        }
+
+        match self.scan_all(pattern)?.as_slice() {
+            [] => Err(anyhow!("failed to scan")),
+            [offset] => Ok(*offset),
+            offsets => Err(anyhow!(
+                "signature is ambiguous, found {}",
+                Self::describe_matches(offsets)
+            )),
+        }
+    }
+
+    fn describe_matches(offsets: &[usize]) -> String {
+        format!(
+            "{} matches: {}",
+            offsets.len(),
+            offsets
+                .iter()
+                .map(|o| format!("{:X}", o))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn cache_key(signature: &Signature) -> CacheKey {
+        match *signature {
+            Signature::Function(pattern) => CacheKey::Regular(pattern.to_owned()),
+            Signature::RelativeCallsite(pattern) => CacheKey::RelativeCallsite(pattern.to_owned()),
+            Signature::Relative(pattern, operand) => {
+                CacheKey::Relative(pattern.to_owned(), operand)
+            }
+        }
+    }
+
+    fn target_from_match(&self, key: &CacheKey, match_offset: usize) -> anyhow::Result<usize> {
+        match key.operand() {
+            Some(operand) => {
+                self.resolve_relative_operand(match_offset + operand.instruction_offset, operand)
+            }
+            None => Ok(match_offset),
+        }
+    }
+
+    /// Returns the cached location for `key`, or finds its match with `find` and caches
+    /// the location it leads to.
+    fn resolve_key(
+        &mut self,
+        key: CacheKey,
+        find: impl FnOnce(&Self) -> anyhow::Result<usize>,
+    ) -> anyhow::Result<*mut u8> {
+        let offset = if let Some(entry) = self.cache.get(&key) {
+            entry.offset
+        } else {
+            let match_offset = find(self)?;
+            let offset = self.target_from_match(&key, match_offset)?;
+            self.cache.insert(
+                key,
+                CacheEntry {
+                    match_offset,
+                    offset,
+                },
+            );
+            offset
+        };
+
+        Ok(self.rel_to_abs_addr(offset))
+    }
+
+    /// Resolves all of the uncached `signatures` in a single pass over the code section,
+    /// populating the cache so that subsequent calls to `resolve` don't need to scan.
+    /// Signatures that can't be resolved are skipped; `resolve` will report their errors.
+    /// When strict, the signatures that matched more than once are then reported together
+    /// with all of their matches, after the rest have been cached.
+    pub fn scan_batch(
+        &mut self,
+        signatures: impl IntoIterator<Item = Signature>,
+    ) -> anyhow::Result<()> {
+        let pending: Vec<_> = signatures
+            .into_iter()
+            .filter(|s| !self.cache.contains_key(&Self::cache_key(s)))
+            .collect();
+        let patterns = pending
+            .iter()
+            .map(|s| Pattern::parse(s.pattern()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let (code_offset, code) = self.code();
+        let matches = Pattern::scan_many(&patterns, code);
+
+        let mut resolved = vec![];
+        let mut ambiguous = vec![];
+        for (signature, matches) in pending.into_iter().zip(matches) {
+            let match_offset = match matches.as_slice() {
+                [] => continue,
+                [offset] => code_offset + offset,
+                [offset, ..] if !self.strict => code_offset + offset,
+                offsets => {
+                    let offsets: Vec<_> = offsets.iter().map(|o| code_offset + o).collect();
+                    ambiguous.push(format!(
+                        "{} ({})",
+                        signature.pattern(),
+                        Self::describe_matches(&offsets)
+                    ));
+                    continue;
+                }
+            };
+            let key = Self::cache_key(&signature);
+            if let Ok(offset) = self.target_from_match(&key, match_offset) {
+                resolved.push((
+                    key,
+                    CacheEntry {
+                        match_offset,
+                        offset,
+                    },
+                ));
+            }
+        }
+        self.cache.extend(resolved);
+
+        if !ambiguous.is_empty() {
+            return Err(anyhow!(
+                "{} signatures are ambiguous:\n{}",
+                ambiguous.len(),
+                ambiguous.join("\n")
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn resolve(&mut self, signature: Signature) -> anyhow::Result<*mut u8> {
+        match signature {
+            Signature::Function(pattern) => self.scan(pattern),
+            Signature::RelativeCallsite(pattern) => self.scan_for_relative_callsite(pattern),
+            Signature::Relative(pattern, operand) => self.scan_for_relative(pattern, operand),
+        }
+    }
+
+    /// Resolves `signature`, and extracts the captures of its pattern from its match.
+    pub fn resolve_captures(&mut self, signature: Signature) -> anyhow::Result<Captures> {
+        self.resolve(signature)?;
+        self.captures_for_key(&Self::cache_key(&signature))
+    }
+
+    fn captures_for_key(&self, key: &CacheKey) -> anyhow::Result<Captures> {
+        let entry = self
+            .cache
+            .get(key)
+            .ok_or_else(|| anyhow!("{} hasn't been resolved", key.pattern()))?;
+        Pattern::parse(key.pattern())?.captures_at(self.as_bytes(), entry.match_offset)
+    }
+
+    pub fn scan(&mut self, pattern: &str) -> anyhow::Result<*mut u8> {
+        self.resolve_key(CacheKey::Regular(pattern.to_owned()), |m| {
+            m.scan_unique(pattern)
+        })
+    }
+
+    pub fn scan_for_relative_callsite(&mut self, pattern: &str) -> anyhow::Result<*mut u8> {
+        self.resolve_key(CacheKey::RelativeCallsite(pattern.to_owned()), |m| {
+            m.scan_unique(pattern)
+        })
+    }
+
+    pub fn scan_for_relative(
+        &mut self,
+        pattern: &str,
+        operand: RelativeOperand,
+    ) -> anyhow::Result<*mut u8> {
+        self.resolve_key(CacheKey::Relative(pattern.to_owned(), operand), |m| {
+            m.scan_unique(pattern)
+        })
+    }
+
+    /// Returns the offset targeted by `operand` of the instruction at `instruction`.
+    pub fn resolve_relative_operand(
+        &self,
+        instruction: usize,
+        operand: RelativeOperand,
+    ) -> anyhow::Result<usize> {
+        let start = instruction + operand.operand_offset;
+        let bytes = self
+            .as_bytes()
+            .get(start..start + operand.operand_size)
+            .ok_or_else(|| anyhow!("operand at {:X} extends past the end of the image", start))?;
+        let displacement = match operand.operand_size {
+            1 => bytes[0] as i8 as isize,
+            4 => i32::from_le_bytes(bytes.try_into()?) as isize,
+            size => return Err(anyhow!("unsupported operand size {}", size)),
+        };
+
+        Ok(
+            (instruction as isize + operand.instruction_length as isize + displacement)
+                .try_into()?,
+        )
+    }
+
+    pub fn scan_after_ptr(&mut self, base: *const u8, pattern: &str) -> anyhow::Result<*mut u8> {
+        let base_offset = self.abs_to_rel_addr(base) as usize;
+
+        self.resolve_key(CacheKey::AfterPtr(pattern.to_owned(), base_offset), |m| {
+            let slice = &m.as_bytes()[base_offset..];
+
+            let offset_from_base = Pattern::parse(pattern)?
+                .scan_first(slice)
+                .ok_or_else(|| anyhow!("failed to scan"))?;
+
+            Ok(base_offset + offset_from_base)
+        })
+    }
+
+    /// Like `scan_after_ptr`, but extracts the captures of `pattern` from its match.
+    pub fn scan_after_ptr_captures(
+        &mut self,
+        base: *const u8,
+        pattern: &str,
+    ) -> anyhow::Result<Captures> {
+        self.scan_after_ptr(base, pattern)?;
+        let base_offset = self.abs_to_rel_addr(base) as usize;
+        self.captures_for_key(&CacheKey::AfterPtr(pattern.to_owned(), base_offset))
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_ref().map(Path::new)
+    }
+
+    pub fn directory(&self) -> Option<&Path> {
+        self.path().and_then(Path::parent)
+    }
+
+    pub fn filename(&self) -> Option<String> {
+        self.path()?
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_string())
+    }
+
+    pub fn abs_to_rel_addr(&self, p: *const u8) -> isize {
+        (p as isize).wrapping_sub(self.base as isize)
+    }
+
+    pub fn rel_to_abs_addr(&self, offset: usize) -> *mut u8 {
+        self.rel_to_abs_addr_isize(offset as isize)
+    }
+
+    pub fn rel_to_abs_addr_isize(&self, offset: isize) -> *mut u8 {
+        self.base.wrapping_offset(offset)
+    }
+
+    #[cfg(windows)]
+    pub fn handle(&self) -> HINSTANCE {
+        self.handle
+    }
+
+    /// Reads the TLS index the loader assigned to this module. Only meaningful for a
+    /// module that's loaded into this process.
+    pub fn tls_index(&self) -> anyhow::Result<u32> {
+        let headers = self.headers()?;
+        let tls = headers
+            .tls(self.as_bytes())?
+            .ok_or_else(|| anyhow!("module has no TLS directory"))?;
+
+        // The directory holds virtual addresses, which the loader relocates alongside
+        // the image base in the headers, so the difference between them is the RVA.
+        let index_rva = tls.address_of_index.wrapping_sub(headers.image_base) as isize;
+        Ok(unsafe { *(self.rel_to_abs_addr_isize(index_rva) as *const u32) })
+    }
+
+    fn cache_filename(&self) -> anyhow::Result<String> {
+        self.path()
+            .map(|p| p.with_extension("json"))
+            .and_then(|p| {
+                p.file_name()
+                    .and_then(|f| f.to_str())
+                    .map(|f| f.to_string())
+            })
+            .ok_or_else(|| anyhow!("failed to get filename"))
+    }
+
+    /// Checks that the pattern for `key` still matches where it was cached, and that
+    /// it still leads to the same location.
+    fn verify_cache_entry(&self, key: &CacheKey, entry: &CacheEntry) -> bool {
+        let matches = Pattern::parse(key.pattern())
+            .ok()
+            .zip(self.as_bytes().get(entry.match_offset..))
+            .is_some_and(|(pattern, bytes)| pattern.matches(bytes));
+
+        matches && self.target_from_match(key, entry.match_offset).ok() == Some(entry.offset)
+    }
+
+    /// Loads the signature cache for this module from `cache_dir`, and remembers the
+    /// location so that the cache can be saved there later.
+    pub fn load_cache(&mut self, cache_dir: &Path) -> anyhow::Result<()> {
+        std::fs::create_dir_all(cache_dir)?;
+        let path = cache_dir.join(self.cache_filename()?);
+        self.cache_path = Some(path.clone());
+        if !path.is_file() {
+            return Ok(());
+        }
+
+        self.read_cache(&path)
+    }
+
+    /// Reads the signature cache at `path`, without saving to it later. Entries are only
+    /// used if they're for this build of the module and can be verified against the
+    /// image; the rest are kept as hints for `recover_signature`.
+    pub fn read_cache(&mut self, path: &Path) -> anyhow::Result<()> {
+        let buf = std::fs::read(path)?;
+        let buf = std::str::from_utf8(&buf)?;
+
+        // An unreadable cache is rebuilt from scratch and overwritten when saved.
+        let (entries, current): (Vec<(CacheKey, CacheEntry)>, bool) =
+            match serde_json::from_str(buf) {
+                Ok(AnySerializedCache::Current(cache)) => {
+                    let current =
+                        cache.version == CACHE_VERSION && cache.identity == self.identity()?;
+                    (cache.entries, current)
+                }
+                // The legacy cache didn't record where the pattern matched, so only
+                // entries at their match can be migrated. It didn't record which build it
+                // was for either, so its entries are only ever hints, and are scanned for
+                // (and checked for ambiguity) like everything else.
+                Ok(AnySerializedCache::Legacy(cache)) => (
+                    cache
+                        .entries
+                        .into_iter()
+                        .filter(|(key, _)| key.operand().is_none())
+                        .map(|(key, offset)| {
+                            let entry = CacheEntry {
+                                match_offset: offset,
+                                offset,
+                            };
+                            (key, entry)
+                        })
+                        .collect(),
+                    false,
+                ),
+                Err(_) => return Ok(()),
+            };
+
+        for (key, entry) in entries {
+            if current && self.verify_cache_entry(&key, &entry) {
+                self.cache.insert(key, entry);
+            } else {
+                self.stale_cache.insert(key, entry);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_cache(&self) -> anyhow::Result<()> {
+        let path = match &self.cache_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if self.cache.is_empty() {
+            return Ok(());
+        }
+
+        let serialized_cache = SerializedCache {
+            version: CACHE_VERSION,
+            identity: self.identity()?,
+            entries: self.cache.clone().into_iter().collect(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&serialized_cache)?)?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        let _ = self.save_cache();
+    }
+}
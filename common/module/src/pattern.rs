@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+
+use anyhow::anyhow;
+
+/// How the bytes of a capture are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKind {
+    U8,
+    U16,
+    U32,
+    U64,
+    /// A displacement relative to the end of the capture, which is assumed to be the
+    /// end of its instruction; captures the offset of its target.
+    Rel8,
+    Rel32,
+}
+
+impl CaptureKind {
+    fn parse(kind: &str) -> Option<CaptureKind> {
+        Some(match kind {
+            "u8" => CaptureKind::U8,
+            "u16" => CaptureKind::U16,
+            "u32" => CaptureKind::U32,
+            "u64" => CaptureKind::U64,
+            "rel8" => CaptureKind::Rel8,
+            "rel32" => CaptureKind::Rel32,
+            _ => return None,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            CaptureKind::U8 | CaptureKind::Rel8 => 1,
+            CaptureKind::U16 => 2,
+            CaptureKind::U32 | CaptureKind::Rel32 => 4,
+            CaptureKind::U64 => 8,
+        }
+    }
+}
+
+impl fmt::Display for CaptureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            CaptureKind::U8 => "u8",
+            CaptureKind::U16 => "u16",
+            CaptureKind::U32 => "u32",
+            CaptureKind::U64 => "u64",
+            CaptureKind::Rel8 => "rel8",
+            CaptureKind::Rel32 => "rel32",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// A named slot in a pattern, written as `<name:kind>`, whose bytes match anything and
+/// are extracted from each match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    pub name: String,
+    /// Offset of the capture from the start of the pattern.
+    pub offset: usize,
+    pub kind: CaptureKind,
+}
+
+/// The values extracted from a match of a pattern with captures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures {
+    pub match_offset: usize,
+    values: HashMap<String, usize>,
+}
+
+impl Captures {
+    /// Returns the value of the capture called `name`. For relative captures, this is
+    /// the offset of their target within the data that was scanned.
+    pub fn get(&self, name: &str) -> anyhow::Result<usize> {
+        self.values
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow!("no capture called {}", name))
+    }
+}
+
+/// A byte pattern in the usual `48 8B ? ?` form, where `?` (or `??`) matches any byte.
+/// Patterns can also contain named captures, e.g. `48 8B 58 <offset:u8>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    bytes: Vec<Option<u8>>,
+    // The first concrete byte; candidates are only checked where it occurs.
+    anchor: usize,
+    captures: Vec<Capture>,
+}
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> anyhow::Result<Pattern> {
+        let mut bytes = vec![];
+        let mut captures: Vec<Capture> = vec![];
+        for segment in pattern.split_ascii_whitespace() {
+            match segment {
+                "?" | "??" => bytes.push(None),
+                _ if segment.starts_with('<') => {
+                    let (name, kind) = segment
+                        .strip_prefix('<')
+                        .and_then(|s| s.strip_suffix('>'))
+                        .and_then(|s| s.split_once(':'))
+                        .ok_or_else(|| {
+                            anyhow!("invalid capture `{}` in pattern `{}`", segment, pattern)
+                        })?;
+                    let kind = CaptureKind::parse(kind).ok_or_else(|| {
+                        anyhow!("invalid capture type `{}` in pattern `{}`", kind, pattern)
+                    })?;
+                    if name.is_empty() || captures.iter().any(|c| c.name == name) {
+                        return Err(anyhow!(
+                            "invalid capture name `{}` in pattern `{}`",
+                            name,
+                            pattern
+                        ));
+                    }
+
+                    captures.push(Capture {
+                        name: name.to_string(),
+                        offset: bytes.len(),
+                        kind,
+                    });
+                    bytes.resize(bytes.len() + kind.size(), None);
+                }
+                _ => bytes.push(Some(u8::from_str_radix(segment, 16).map_err(|_| {
+                    anyhow!("invalid byte `{}` in pattern `{}`", segment, pattern)
+                })?)),
+            }
+        }
+
+        Pattern::with_captures(bytes, captures)
+    }
+
+    /// Builds a pattern from bytes, where `None` matches any byte.
+    pub fn from_bytes(bytes: Vec<Option<u8>>) -> anyhow::Result<Pattern> {
+        let anchor = bytes
+            .iter()
+            .position(Option::is_some)
+            .ok_or_else(|| anyhow!("pattern has no concrete bytes"))?;
+
+        Ok(Pattern {
+            bytes,
+            anchor,
+            captures: vec![],
+        })
+    }
+
+    pub(crate) fn with_captures(
+        bytes: Vec<Option<u8>>,
+        captures: Vec<Capture>,
+    ) -> anyhow::Result<Pattern> {
+        let mut pattern = Pattern::from_bytes(bytes)?;
+        pattern.captures = captures;
+        Ok(pattern)
+    }
+
+    pub(crate) fn bytes(&self) -> &[Option<u8>] {
+        &self.bytes
+    }
+
+    pub fn captures(&self) -> &[Capture] {
+        &self.captures
+    }
+
+    /// Extracts the captures of a match of this pattern at `offset` in `data`.
+    pub fn captures_at(&self, data: &[u8], offset: usize) -> anyhow::Result<Captures> {
+        let bytes = data
+            .get(offset..)
+            .filter(|bytes| self.matches(bytes))
+            .ok_or_else(|| anyhow!("pattern `{}` doesn't match at {:X}", self, offset))?;
+
+        let values =
+            self.captures
+                .iter()
+                .map(|capture| {
+                    let start = capture.offset;
+                    let end = start + capture.kind.size();
+                    let raw = &bytes[start..end];
+                    let value = match capture.kind {
+                        CaptureKind::U8 => raw[0] as usize,
+                        CaptureKind::U16 => u16::from_le_bytes(raw.try_into()?) as usize,
+                        CaptureKind::U32 => u32::from_le_bytes(raw.try_into()?) as usize,
+                        CaptureKind::U64 => u64::from_le_bytes(raw.try_into()?) as usize,
+                        CaptureKind::Rel8 => (offset + end).wrapping_add(raw[0] as i8 as usize),
+                        CaptureKind::Rel32 => (offset + end)
+                            .wrapping_add(i32::from_le_bytes(raw.try_into()?) as usize),
+                    };
+                    Ok((capture.name.clone(), value))
+                })
+                .collect::<anyhow::Result<_>>()?;
+
+        Ok(Captures {
+            match_offset: offset,
+            values,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn anchor_byte(&self) -> u8 {
+        self.bytes[self.anchor].unwrap()
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() >= self.bytes.len()
+            && self
+                .bytes
+                .iter()
+                .zip(data)
+                .all(|(p, b)| p.is_none_or(|p| p == *b))
+    }
+
+    /// Returns the offsets of every match of this pattern in `data`.
+    pub fn scan(&self, data: &[u8]) -> Vec<usize> {
+        let anchor_byte = self.anchor_byte();
+        let end = (data.len() + self.anchor + 1).saturating_sub(self.bytes.len());
+
+        (self.anchor..end)
+            .filter(|&i| data[i] == anchor_byte)
+            .map(|i| i - self.anchor)
+            .filter(|&start| self.matches(&data[start..]))
+            .collect()
+    }
+
+    /// Returns the offset of the first match of this pattern in `data`.
+    pub fn scan_first(&self, data: &[u8]) -> Option<usize> {
+        let anchor_byte = self.anchor_byte();
+        let end = (data.len() + self.anchor + 1).saturating_sub(self.bytes.len());
+
+        (self.anchor..end)
+            .filter(|&i| data[i] == anchor_byte)
+            .map(|i| i - self.anchor)
+            .find(|&start| self.matches(&data[start..]))
+    }
+
+    /// Finds every match of every pattern in a single pass over `data`, returning
+    /// the matches for each pattern in the order the patterns were given.
+    pub fn scan_many(patterns: &[Pattern], data: &[u8]) -> Vec<Vec<usize>> {
+        let mut by_anchor_byte: Vec<Vec<usize>> = vec![vec![]; 256];
+        for (index, pattern) in patterns.iter().enumerate() {
+            by_anchor_byte[pattern.anchor_byte() as usize].push(index);
+        }
+
+        let mut matches = vec![vec![]; patterns.len()];
+        for (i, byte) in data.iter().enumerate() {
+            for &index in &by_anchor_byte[*byte as usize] {
+                let pattern = &patterns[index];
+                let start = match i.checked_sub(pattern.anchor) {
+                    Some(start) => start,
+                    None => continue,
+                };
+                if pattern.matches(&data[start..]) {
+                    matches[index].push(start);
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut segments = vec![];
+        let mut offset = 0;
+        while offset < self.bytes.len() {
+            if let Some(capture) = self.captures.iter().find(|c| c.offset == offset) {
+                segments.push(format!("<{}:{}>", capture.name, capture.kind));
+                offset += capture.kind.size();
+            } else {
+                segments.push(
+                    self.bytes[offset].map_or_else(|| "?".to_string(), |b| format!("{:02X}", b)),
+                );
+                offset += 1;
+            }
+        }
+        write!(f, "{}", segments.join(" "))
+    }
+}
@@ -0,0 +1,499 @@
+//! Parsing of PE32+ headers and the data directories we care about. Everything other
+//! than `Headers::parse` and `Headers::map_image` operates on a mapped image, where
+//! offsets are RVAs; this is the case for both loaded modules and `Module::from_file`.
+use std::convert::TryInto;
+use std::ops::Range;
+
+use anyhow::anyhow;
+
+const SECTION_HEADER_SIZE: usize = 40;
+const PE32_PLUS_MAGIC: u16 = 0x20B;
+const IMAGE_REL_BASED_DIR64: u16 = 10;
+const IMAGE_DEBUG_TYPE_CODEVIEW: u32 = 2;
+const UNW_FLAG_CHAININFO: u8 = 0x4;
+
+pub mod directory {
+    pub const EXPORT: usize = 0;
+    pub const IMPORT: usize = 1;
+    pub const EXCEPTION: usize = 3;
+    pub const BASE_RELOCATION: usize = 5;
+    pub const DEBUG: usize = 6;
+    pub const TLS: usize = 9;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    pub virtual_address: u32,
+    pub virtual_size: u32,
+    pub pointer_to_raw_data: u32,
+    pub size_of_raw_data: u32,
+    pub characteristics: u32,
+}
+
+impl Section {
+    pub fn range(&self) -> Range<usize> {
+        let start = self.virtual_address as usize;
+        start..start + self.virtual_size as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DataDirectory {
+    pub virtual_address: u32,
+    pub size: u32,
+}
+
+/// `IMAGE_TLS_DIRECTORY64`. These are virtual addresses, not RVAs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlsDirectory {
+    pub start_address_of_raw_data: u64,
+    pub end_address_of_raw_data: u64,
+    pub address_of_index: u64,
+    pub address_of_callbacks: u64,
+}
+
+/// An entry in `.pdata`, describing the bounds of a function (or a chunk of one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeFunction {
+    pub begin: u32,
+    pub end: u32,
+    pub unwind_info: u32,
+}
+
+impl RuntimeFunction {
+    pub fn range(&self) -> Range<usize> {
+        self.begin as usize..self.end as usize
+    }
+}
+
+/// `.pdata` parsed into something that can be searched: the range of each chunk, sorted by
+/// address, along with the range of the function it belongs to.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionTable {
+    chunks: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl FunctionTable {
+    /// Returns the bounds of the function containing `offset`. Functions that have been
+    /// split into chunks resolve to their main chunk.
+    pub fn bounds(&self, offset: usize) -> Option<Range<usize>> {
+        let index = self
+            .chunks
+            .partition_point(|(chunk, _)| chunk.end <= offset);
+        match self.chunks.get(index) {
+            Some((chunk, function)) if chunk.contains(&offset) => Some(function.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The `RSDS` CodeView record, which identifies the PDB matching the image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeView {
+    pub guid: [u8; 16],
+    pub age: u32,
+    pub pdb_path: String,
+}
+
+impl CodeView {
+    /// Formats the GUID and age as symbol servers expect them.
+    pub fn pdb_id(&self) -> String {
+        let g = &self.guid;
+        let data1 = u32::from_le_bytes(g[0..4].try_into().unwrap());
+        let data2 = u16::from_le_bytes(g[4..6].try_into().unwrap());
+        let data3 = u16::from_le_bytes(g[6..8].try_into().unwrap());
+        let data4: String = g[8..].iter().map(|b| format!("{:02X}", b)).collect();
+        format!(
+            "{:08X}{:04X}{:04X}{}{:X}",
+            data1, data2, data3, data4, self.age
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportName {
+    Name(String),
+    Ordinal(u16),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedFunction {
+    pub name: ImportName,
+    /// Where the address of the function is written by the loader.
+    pub iat_entry: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub dll: String,
+    pub functions: Vec<ImportedFunction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub name: Option<String>,
+    pub ordinal: u32,
+    pub rva: u32,
+}
+
+/// The file and optional headers of a PE32+ image, along with its section table.
+#[derive(Debug, Clone)]
+pub struct Headers {
+    pub machine: u16,
+    pub timestamp: u32,
+    pub checksum: u32,
+    pub image_base: u64,
+    pub entry_point: u32,
+    pub size_of_image: u32,
+    pub size_of_headers: u32,
+    pub data_directories: Vec<DataDirectory>,
+    pub sections: Vec<Section>,
+}
+
+pub(crate) fn read_bytes(buf: &[u8], offset: usize, size: usize) -> anyhow::Result<&[u8]> {
+    buf.get(offset..offset + size)
+        .ok_or_else(|| anyhow!("unexpected end of data at {:X}", offset))
+}
+
+pub(crate) fn read_u16(buf: &[u8], offset: usize) -> anyhow::Result<u16> {
+    Ok(u16::from_le_bytes(read_bytes(buf, offset, 2)?.try_into()?))
+}
+
+pub(crate) fn read_u32(buf: &[u8], offset: usize) -> anyhow::Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(buf, offset, 4)?.try_into()?))
+}
+
+pub(crate) fn read_u64(buf: &[u8], offset: usize) -> anyhow::Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(buf, offset, 8)?.try_into()?))
+}
+
+pub(crate) fn read_cstr(buf: &[u8], offset: usize) -> anyhow::Result<String> {
+    let bytes = buf
+        .get(offset..)
+        .ok_or_else(|| anyhow!("string at {:X} is out of bounds", offset))?;
+    let end = bytes
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| anyhow!("unterminated string at {:X}", offset))?;
+    Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+impl Headers {
+    pub fn parse(file: &[u8]) -> anyhow::Result<Headers> {
+        if file.get(0..2) != Some(b"MZ") {
+            return Err(anyhow!("missing DOS header"));
+        }
+
+        let nt_offset = read_u32(file, 0x3C)? as usize;
+        if file.get(nt_offset..nt_offset + 4) != Some(b"PE\0\0") {
+            return Err(anyhow!("missing PE signature"));
+        }
+
+        let coff_offset = nt_offset + 4;
+        let number_of_sections = read_u16(file, coff_offset + 2)? as usize;
+        let size_of_optional_header = read_u16(file, coff_offset + 16)? as usize;
+
+        let optional_offset = coff_offset + 20;
+        if read_u16(file, optional_offset)? != PE32_PLUS_MAGIC {
+            return Err(anyhow!("only PE32+ images are supported"));
+        }
+
+        let number_of_rva_and_sizes = read_u32(file, optional_offset + 108)? as usize;
+        let data_directories = (0..number_of_rva_and_sizes)
+            .map(|i| {
+                let offset = optional_offset + 112 + i * 8;
+                Ok(DataDirectory {
+                    virtual_address: read_u32(file, offset)?,
+                    size: read_u32(file, offset + 4)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let section_table_offset = optional_offset + size_of_optional_header;
+        let sections = (0..number_of_sections)
+            .map(|i| {
+                let offset = section_table_offset + i * SECTION_HEADER_SIZE;
+                let name = read_bytes(file, offset, 8)?;
+                let name = name.split(|b| *b == 0).next().unwrap_or_default();
+                Ok(Section {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    virtual_size: read_u32(file, offset + 8)?,
+                    virtual_address: read_u32(file, offset + 12)?,
+                    size_of_raw_data: read_u32(file, offset + 16)?,
+                    pointer_to_raw_data: read_u32(file, offset + 20)?,
+                    characteristics: read_u32(file, offset + 36)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Headers {
+            machine: read_u16(file, coff_offset)?,
+            timestamp: read_u32(file, coff_offset + 4)?,
+            checksum: read_u32(file, optional_offset + 64)?,
+            entry_point: read_u32(file, optional_offset + 16)?,
+            image_base: read_u64(file, optional_offset + 24)?,
+            size_of_image: read_u32(file, optional_offset + 56)?,
+            size_of_headers: read_u32(file, optional_offset + 60)?,
+            data_directories,
+            sections,
+        })
+    }
+
+    /// Returns the first section called `name`.
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    /// Returns the data directory at `index` (see `directory`), if it's present.
+    pub fn data_directory(&self, index: usize) -> Option<DataDirectory> {
+        self.data_directories
+            .get(index)
+            .copied()
+            .filter(|d| d.virtual_address != 0 && d.size != 0)
+    }
+
+    /// Returns the offsets of every 64-bit address in the mapped `image` that gets
+    /// fixed up by the loader when the image isn't loaded at its preferred base.
+    pub fn relocations(&self, image: &[u8]) -> anyhow::Result<Vec<usize>> {
+        let dir = match self.data_directory(directory::BASE_RELOCATION) {
+            Some(dir) => dir,
+            None => return Ok(vec![]),
+        };
+        let (rva, size) = (dir.virtual_address as usize, dir.size as usize);
+
+        let mut relocations = vec![];
+        let mut block = rva;
+        while block < rva + size {
+            let page = read_u32(image, block)? as usize;
+            let block_size = read_u32(image, block + 4)? as usize;
+            if block_size < 8 {
+                break;
+            }
+
+            for entry in (block + 8..block + block_size).step_by(2) {
+                let entry = read_u16(image, entry)?;
+                if entry >> 12 == IMAGE_REL_BASED_DIR64 {
+                    relocations.push(page + (entry & 0xFFF) as usize);
+                }
+            }
+
+            block += block_size;
+        }
+
+        Ok(relocations)
+    }
+
+    pub fn tls(&self, image: &[u8]) -> anyhow::Result<Option<TlsDirectory>> {
+        let dir = match self.data_directory(directory::TLS) {
+            Some(dir) => dir.virtual_address as usize,
+            None => return Ok(None),
+        };
+
+        Ok(Some(TlsDirectory {
+            start_address_of_raw_data: read_u64(image, dir)?,
+            end_address_of_raw_data: read_u64(image, dir + 8)?,
+            address_of_index: read_u64(image, dir + 16)?,
+            address_of_callbacks: read_u64(image, dir + 24)?,
+        }))
+    }
+
+    /// Returns the contents of `.pdata`, which the linker sorts by address.
+    pub fn runtime_functions(&self, image: &[u8]) -> anyhow::Result<Vec<RuntimeFunction>> {
+        let dir = match self.data_directory(directory::EXCEPTION) {
+            Some(dir) => dir,
+            None => return Ok(vec![]),
+        };
+
+        (0..dir.size as usize / 12)
+            .map(|i| {
+                let offset = dir.virtual_address as usize + i * 12;
+                Ok(RuntimeFunction {
+                    begin: read_u32(image, offset)?,
+                    end: read_u32(image, offset + 4)?,
+                    unwind_info: read_u32(image, offset + 8)?,
+                })
+            })
+            .collect()
+    }
+
+    pub fn function_table(&self, image: &[u8]) -> anyhow::Result<FunctionTable> {
+        let chunks = self
+            .runtime_functions(image)?
+            .into_iter()
+            .map(|chunk| {
+                let mut function = chunk;
+                while let Some(parent) = self.chained_runtime_function(image, &function)? {
+                    function = parent;
+                }
+                Ok((chunk.range(), function.range()))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(FunctionTable { chunks })
+    }
+
+    /// If `function` is a chunk of a larger function (i.e. its unwind info is chained),
+    /// returns the entry it's chained to.
+    pub fn chained_runtime_function(
+        &self,
+        image: &[u8],
+        function: &RuntimeFunction,
+    ) -> anyhow::Result<Option<RuntimeFunction>> {
+        let unwind_info = function.unwind_info as usize;
+        let flags = read_bytes(image, unwind_info, 1)?[0] >> 3;
+        if flags & UNW_FLAG_CHAININFO == 0 {
+            return Ok(None);
+        }
+
+        // The chained entry follows the unwind codes, which are padded to an even count.
+        let count_of_codes = read_bytes(image, unwind_info + 2, 1)?[0] as usize;
+        let offset = unwind_info + 4 + ((count_of_codes + 1) & !1) * 2;
+        Ok(Some(RuntimeFunction {
+            begin: read_u32(image, offset)?,
+            end: read_u32(image, offset + 4)?,
+            unwind_info: read_u32(image, offset + 8)?,
+        }))
+    }
+
+    pub fn codeview(&self, image: &[u8]) -> anyhow::Result<Option<CodeView>> {
+        let dir = match self.data_directory(directory::DEBUG) {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+
+        for i in 0..dir.size as usize / 28 {
+            let entry = dir.virtual_address as usize + i * 28;
+            if read_u32(image, entry + 12)? != IMAGE_DEBUG_TYPE_CODEVIEW {
+                continue;
+            }
+
+            let data = read_u32(image, entry + 20)? as usize;
+            if read_bytes(image, data, 4)? != b"RSDS" {
+                continue;
+            }
+
+            return Ok(Some(CodeView {
+                guid: read_bytes(image, data + 4, 16)?.try_into()?,
+                age: read_u32(image, data + 20)?,
+                pdb_path: read_cstr(image, data + 24)?,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    pub fn imports(&self, image: &[u8]) -> anyhow::Result<Vec<Import>> {
+        let dir = match self.data_directory(directory::IMPORT) {
+            Some(dir) => dir.virtual_address as usize,
+            None => return Ok(vec![]),
+        };
+
+        let mut imports = vec![];
+        for descriptor in (dir..).step_by(20) {
+            let original_first_thunk = read_u32(image, descriptor)? as usize;
+            let name = read_u32(image, descriptor + 12)? as usize;
+            let first_thunk = read_u32(image, descriptor + 16)? as usize;
+            if name == 0 && first_thunk == 0 {
+                break;
+            }
+
+            // The lookup table is optional; the IAT holds the same data until it's bound.
+            let lookup = if original_first_thunk != 0 {
+                original_first_thunk
+            } else {
+                first_thunk
+            };
+
+            let mut functions = vec![];
+            for i in 0.. {
+                let thunk = read_u64(image, lookup + i * 8)?;
+                if thunk == 0 {
+                    break;
+                }
+
+                let name = if thunk & (1 << 63) != 0 {
+                    ImportName::Ordinal(thunk as u16)
+                } else {
+                    // Skip the hint
+                    ImportName::Name(read_cstr(image, (thunk as u32) as usize + 2)?)
+                };
+                functions.push(ImportedFunction {
+                    name,
+                    iat_entry: first_thunk + i * 8,
+                });
+            }
+
+            imports.push(Import {
+                dll: read_cstr(image, name)?,
+                functions,
+            });
+        }
+
+        Ok(imports)
+    }
+
+    pub fn exports(&self, image: &[u8]) -> anyhow::Result<Vec<Export>> {
+        let dir = match self.data_directory(directory::EXPORT) {
+            Some(dir) => dir.virtual_address as usize,
+            None => return Ok(vec![]),
+        };
+
+        let base = read_u32(image, dir + 16)?;
+        let number_of_functions = read_u32(image, dir + 20)? as usize;
+        let number_of_names = read_u32(image, dir + 24)? as usize;
+        let address_of_functions = read_u32(image, dir + 28)? as usize;
+        let address_of_names = read_u32(image, dir + 32)? as usize;
+        let address_of_name_ordinals = read_u32(image, dir + 36)? as usize;
+
+        let mut exports = (0..number_of_functions)
+            .map(|i| {
+                Ok(Export {
+                    name: None,
+                    ordinal: base + i as u32,
+                    rva: read_u32(image, address_of_functions + i * 4)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for i in 0..number_of_names {
+            let name = read_u32(image, address_of_names + i * 4)? as usize;
+            let index = read_u16(image, address_of_name_ordinals + i * 2)? as usize;
+            if let Some(export) = exports.get_mut(index) {
+                export.name = Some(read_cstr(image, name)?);
+            }
+        }
+
+        // Unused ordinals have no address.
+        exports.retain(|e| e.rva != 0);
+        Ok(exports)
+    }
+
+    /// Copies the headers and each section's raw data to where they would be in memory.
+    pub fn map_image(&self, file: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut image = vec![0u8; self.size_of_image as usize];
+
+        let headers_size = (self.size_of_headers as usize)
+            .min(file.len())
+            .min(image.len());
+        image[..headers_size].copy_from_slice(&file[..headers_size]);
+
+        for section in &self.sections {
+            // Raw data is padded to the file alignment, so it can exceed the virtual size.
+            let size = match section.virtual_size {
+                0 => section.size_of_raw_data,
+                virtual_size => section.size_of_raw_data.min(virtual_size),
+            } as usize;
+            let src_start = section.pointer_to_raw_data as usize;
+            let dst_start = section.virtual_address as usize;
+            let src = file
+                .get(src_start..src_start + size)
+                .ok_or_else(|| anyhow!("section data at {:X} is out of bounds", src_start))?;
+            let dst = image
+                .get_mut(dst_start..dst_start + size)
+                .ok_or_else(|| anyhow!("section at {:X} is outside the image", dst_start))?;
+            dst.copy_from_slice(src);
+        }
+
+        Ok(image)
+    }
+}
@@ -0,0 +1,229 @@
+//! Recovery of signatures that no longer match, typically after a game patch. Instead of
+//! an exact scan, we look for the places in the code that are the fewest edits (byte
+//! insertions, deletions and substitutions) away from the pattern, and rewrite the
+//! pattern to match there.
+use crate::{Capture, Module, Pattern, Signature};
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+
+// The approximate search packs the pattern into the bits of a u64.
+const MAX_PATTERN_LENGTH: usize = 64;
+const MAX_CANDIDATES: usize = 8;
+// How far either side of a signature's previous match to search with a doubled budget;
+// code tends not to move far between patches.
+const NEIGHBOURHOOD: usize = 0x10_0000;
+
+/// A possible location for a signature that no longer matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// Where the updated pattern matches.
+    pub offset: usize,
+    /// The number of byte edits between the pattern and the code at `offset`.
+    pub distance: usize,
+    /// How much of the pattern survived, from 0 to 1.
+    pub similarity: f32,
+    /// How far `offset` is from where the signature matched previously, if known.
+    pub displacement: Option<isize>,
+    /// The signature's pattern, updated to match at `offset`. Wildcards and captures
+    /// are kept where they line up; everything else is taken from the code.
+    pub pattern: String,
+    /// How many places in the code `pattern` matches; it's only usable if this is 1.
+    pub matches: usize,
+}
+
+/// Returns the end of every run of places in `data` where `pattern` matches with at
+/// most `max_distance` edits, along with the smallest distance within that run. This
+/// is Myers' bit-parallel algorithm, with wildcards matching every byte.
+fn search(pattern: &[Option<u8>], data: &[u8], max_distance: usize) -> Vec<(usize, usize)> {
+    let mut peq = [0u64; 256];
+    for (i, byte) in pattern.iter().enumerate() {
+        match byte {
+            Some(byte) => peq[*byte as usize] |= 1 << i,
+            None => peq.iter_mut().for_each(|eq| *eq |= 1 << i),
+        }
+    }
+
+    let last = 1u64 << (pattern.len() - 1);
+    let (mut pv, mut mv, mut distance) = (!0u64, 0u64, pattern.len());
+    let mut hits: Vec<(usize, usize)> = vec![];
+    let mut previous_hit = None;
+    for (i, byte) in data.iter().enumerate() {
+        let eq = peq[*byte as usize];
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let ph = mv | !(xh | pv);
+        let mh = pv & xh;
+        if ph & last != 0 {
+            distance += 1;
+        } else if mh & last != 0 {
+            distance -= 1;
+        }
+        let (ph, mh) = (ph << 1, mh << 1);
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+
+        if distance > max_distance {
+            continue;
+        }
+        let end = i + 1;
+        match hits.last_mut() {
+            Some(hit) if previous_hit == Some(i) => {
+                if distance < hit.1 {
+                    *hit = (end, distance);
+                }
+            }
+            _ => hits.push((end, distance)),
+        }
+        previous_hit = Some(end);
+    }
+
+    hits
+}
+
+/// Aligns `pattern` so that it ends at the end of `window`, returning where it starts,
+/// its distance, and the pattern rewritten to match the window.
+fn align(pattern: &Pattern, window: &[u8]) -> anyhow::Result<(usize, usize, Pattern)> {
+    let bytes = pattern.bytes();
+    let (m, n) = (bytes.len(), window.len());
+
+    // distances[i][j]: edits to match the first i bytes of the pattern, ending at j.
+    // Matches can start anywhere, so the first row is free.
+    let mut distances = vec![vec![0usize; n + 1]; m + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let substitution = match bytes[i - 1] {
+                Some(byte) if byte != window[j - 1] => 1,
+                _ => 0,
+            };
+            distances[i][j] = (distances[i - 1][j - 1] + substitution)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+        }
+    }
+
+    // Walk back from the end, preferring to line bytes up.
+    let mut updated = vec![];
+    let mut new_index: Vec<Option<usize>> = vec![None; m];
+    let (mut i, mut j) = (m, n);
+    while i > 0 {
+        let substitution = match bytes[i - 1] {
+            Some(byte) if j > 0 && byte != window[j - 1] => 1,
+            _ => 0,
+        };
+        if j > 0 && distances[i][j] == distances[i - 1][j - 1] + substitution {
+            updated.push(bytes[i - 1].map(|_| window[j - 1]));
+            new_index[i - 1] = Some(updated.len());
+            i -= 1;
+            j -= 1;
+        } else if distances[i][j] == distances[i - 1][j] + 1 {
+            i -= 1;
+        } else {
+            updated.push(Some(window[j - 1]));
+            j -= 1;
+        }
+    }
+    updated.reverse();
+    // The indices were counted from the end.
+    let new_index = |i: usize| new_index[i].map(|index| updated.len() - index);
+
+    // Keep the captures that still line up in their entirety.
+    let captures = pattern
+        .captures()
+        .iter()
+        .filter_map(|capture| {
+            let offset = new_index(capture.offset)?;
+            (0..capture.kind.size())
+                .all(|k| new_index(capture.offset + k) == Some(offset + k))
+                .then(|| Capture {
+                    offset,
+                    ..capture.clone()
+                })
+        })
+        .collect();
+
+    // Bytes of captures that didn't survive are wildcards, but no longer named.
+    Ok((
+        j,
+        distances[m][n],
+        Pattern::with_captures(updated, captures)?,
+    ))
+}
+
+impl Module {
+    /// Searches for places where `signature` almost matches, within `max_distance`
+    /// byte edits of its pattern. If the signature matched somewhere previously (i.e.
+    /// there's a stale entry in the cache), the area around that location is searched
+    /// with twice the budget. Returns the best candidates first.
+    pub fn recover_signature(
+        &self,
+        signature: Signature,
+        max_distance: usize,
+    ) -> anyhow::Result<Vec<Candidate>> {
+        let pattern = Pattern::parse(signature.pattern())?;
+        if pattern.len() > MAX_PATTERN_LENGTH {
+            return Err(anyhow!(
+                "pattern is too long to recover ({} > {} bytes)",
+                pattern.len(),
+                MAX_PATTERN_LENGTH
+            ));
+        }
+
+        let bytes = self.as_bytes();
+        let (code_offset, code) = self.code();
+        let previous = self
+            .stale_cache
+            .get(&Self::cache_key(&signature))
+            .map(|entry| entry.match_offset);
+
+        let mut regions = vec![(code_offset..code_offset + code.len(), max_distance)];
+        if let Some(previous) = previous {
+            let start = previous.saturating_sub(NEIGHBOURHOOD).max(code_offset);
+            let end = (previous + NEIGHBOURHOOD).min(code_offset + code.len());
+            if start < end {
+                regions.push((start..end, max_distance * 2));
+            }
+        }
+
+        // The end of each hit, and its distance.
+        let mut hits: HashMap<usize, usize> = HashMap::new();
+        for (region, max_distance) in regions {
+            for (end, distance) in search(pattern.bytes(), &bytes[region.clone()], max_distance) {
+                let distance_so_far = hits.entry(region.start + end).or_insert(distance);
+                *distance_so_far = (*distance_so_far).min(distance);
+            }
+        }
+        let mut hits: Vec<_> = hits.into_iter().collect();
+        hits.sort_by_key(|(end, distance)| (*distance, *end));
+
+        let mut candidates: Vec<Candidate> = vec![];
+        for (end, _) in hits {
+            let window_start = end.saturating_sub(pattern.len() + max_distance * 2);
+            let (start, distance, updated) = align(&pattern, &bytes[window_start..end])?;
+            let offset = window_start + start;
+            if candidates.iter().any(|c| c.offset == offset) {
+                continue;
+            }
+
+            let updated = updated.to_string();
+            candidates.push(Candidate {
+                offset,
+                distance,
+                similarity: 1.0 - (distance as f32 / pattern.len() as f32).min(1.0),
+                displacement: previous.map(|p| offset as isize - p as isize),
+                matches: self.scan_all(&updated)?.len(),
+                pattern: updated,
+            });
+            if candidates.len() == MAX_CANDIDATES {
+                break;
+            }
+        }
+        candidates.sort_by_key(|c| (c.distance, c.displacement.map(isize::abs)));
+
+        Ok(candidates)
+    }
+}
@@ -0,0 +1,161 @@
+//! Discovery of vtables through MSVC's RTTI. Each vtable is preceded by a pointer to a
+//! complete object locator, which refers to the type descriptor holding the class's
+//! decorated name; we search for that chain in reverse to go from a name to its vtables.
+use crate::{pe, Module, Pattern};
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+
+const COMPLETE_OBJECT_LOCATOR_SIZE: usize = 24;
+const COMPLETE_OBJECT_LOCATOR_SIGNATURE: u32 = 1;
+const TYPE_DESCRIPTOR_NAME: usize = 16;
+
+/// A vtable of a class with RTTI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vtable {
+    pub rva: usize,
+    /// Offset of the subobject using this vtable within the complete object. Classes
+    /// with multiple polymorphic bases have a vtable for each of them.
+    pub offset: u32,
+    /// The class name, demangled where possible (see `demangle`).
+    pub class_name: String,
+}
+
+/// `_RTTICompleteObjectLocator`, minus the fields we don't need.
+struct CompleteObjectLocator {
+    offset: u32,
+    type_descriptor: usize,
+}
+
+/// Returns the decorated names a type descriptor could have for `class_name`, which
+/// depend on whether it was declared as a `class` or a `struct`.
+fn mangle(class_name: &str) -> [String; 2] {
+    let mut scopes: Vec<_> = class_name.split("::").collect();
+    scopes.reverse();
+    let scopes = scopes.join("@");
+    [format!(".?AV{}@@", scopes), format!(".?AU{}@@", scopes)]
+}
+
+/// Turns a decorated name like `.?AVFramework@Framework@System@Client@@` into
+/// `Client::System::Framework::Framework`. Names we can't demangle, like those of
+/// templates and anonymous namespaces, return `None`.
+pub fn demangle(name: &str) -> Option<String> {
+    let scopes = name
+        .strip_prefix(".?AV")
+        .or_else(|| name.strip_prefix(".?AU"))?
+        .strip_suffix("@@")?;
+    if scopes.is_empty() || scopes.contains(['?', '$']) {
+        return None;
+    }
+    Some(scopes.split('@').rev().collect::<Vec<_>>().join("::"))
+}
+
+impl Module {
+    fn complete_object_locator(&self, rva: usize) -> Option<CompleteObjectLocator> {
+        let bytes = self.as_bytes();
+        let signature = pe::read_u32(bytes, rva).ok()?;
+        let self_rva = pe::read_u32(bytes, rva + 20).ok()? as usize;
+        if signature != COMPLETE_OBJECT_LOCATOR_SIGNATURE || self_rva != rva {
+            return None;
+        }
+
+        Some(CompleteObjectLocator {
+            offset: pe::read_u32(bytes, rva + 4).ok()?,
+            type_descriptor: pe::read_u32(bytes, rva + 12).ok()? as usize,
+        })
+    }
+
+    /// Finds the vtables of the class called `class_name` (e.g.
+    /// `Client::System::Framework::Framework`), ordered by their offset.
+    pub fn find_vtables(&self, class_name: &str) -> anyhow::Result<Vec<Vtable>> {
+        let bytes = self.as_bytes();
+        let data = self.section(".data").unwrap_or(0..bytes.len());
+        let rdata = self
+            .section(".rdata")
+            .ok_or_else(|| anyhow!("no .rdata section"))?;
+
+        let mut type_descriptors = vec![];
+        for name in mangle(class_name).iter() {
+            let mut needle: Vec<_> = name.bytes().map(Some).collect();
+            needle.push(Some(0));
+            type_descriptors.extend(
+                Pattern::from_bytes(needle)?
+                    .scan(&bytes[data.clone()])
+                    .into_iter()
+                    // A name too close to the start of the section can't be in a descriptor.
+                    .filter_map(|offset| offset.checked_sub(TYPE_DESCRIPTOR_NAME))
+                    .map(|offset| data.start + offset),
+            );
+        }
+        if type_descriptors.is_empty() {
+            return Err(anyhow!("no type descriptor for {}", class_name));
+        }
+
+        // Keyed on the address the vtables will point to, which is relocated.
+        let locators: HashMap<u64, u32> = (rdata.start..rdata.end)
+            .step_by(4)
+            .take_while(|rva| rva + COMPLETE_OBJECT_LOCATOR_SIZE <= rdata.end)
+            .filter_map(|rva| {
+                let locator = self.complete_object_locator(rva)?;
+                type_descriptors
+                    .contains(&locator.type_descriptor)
+                    .then(|| (self.base as u64 + rva as u64, locator.offset))
+            })
+            .collect();
+
+        let mut vtables: Vec<_> = (rdata.start..rdata.end)
+            .step_by(8)
+            .filter_map(|rva| {
+                let offset = locators.get(&pe::read_u64(bytes, rva).ok()?)?;
+                Some(Vtable {
+                    rva: rva + 8,
+                    offset: *offset,
+                    class_name: class_name.to_string(),
+                })
+            })
+            .collect();
+        vtables.sort_by_key(|v| v.offset);
+
+        Ok(vtables)
+    }
+
+    /// Returns the primary vtable of the class called `class_name`.
+    pub fn find_vtable(&self, class_name: &str) -> anyhow::Result<*mut u8> {
+        let vtable = self
+            .find_vtables(class_name)?
+            .into_iter()
+            .find(|v| v.offset == 0)
+            .ok_or_else(|| anyhow!("no vtable for {}", class_name))?;
+        Ok(self.rel_to_abs_addr(vtable.rva))
+    }
+
+    /// Identifies the class that `vtable` belongs to. Returns `None` if it isn't a
+    /// vtable in this module, or the class has no RTTI.
+    pub fn vtable_class(&self, vtable: *const u8) -> Option<Vtable> {
+        let rva = self.abs_to_rel_addr(vtable);
+        if rva < 8 || rva as usize >= self.as_bytes().len() {
+            return None;
+        }
+        let rva = rva as usize;
+
+        let bytes = self.as_bytes();
+        let locator_address = pe::read_u64(bytes, rva - 8).ok()?;
+        let locator_rva = self.abs_to_rel_addr(locator_address as *const u8);
+        if locator_rva < 0 {
+            return None;
+        }
+        let locator = self.complete_object_locator(locator_rva as usize)?;
+
+        let name = pe::read_cstr(bytes, locator.type_descriptor + TYPE_DESCRIPTOR_NAME).ok()?;
+        if !name.starts_with(".?A") {
+            return None;
+        }
+
+        Some(Vtable {
+            rva,
+            offset: locator.offset,
+            class_name: demangle(&name).unwrap_or(name),
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+/// Describes an instruction operand that holds a displacement relative to the end
+/// of its instruction (i.e. `rel8`/`rel32` branches and RIP-relative memory operands).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RelativeOperand {
+    /// Offset of the instruction from the start of the match.
+    pub instruction_offset: usize,
+    /// Offset of the operand from the start of the instruction.
+    pub operand_offset: usize,
+    /// Size of the operand in bytes; either 1 or 4.
+    pub operand_size: usize,
+    /// Length of the instruction in bytes, including any trailing immediates.
+    pub instruction_length: usize,
+}
+
+impl RelativeOperand {
+    /// `E8 rel32`
+    pub const CALL: RelativeOperand = RelativeOperand::rel32(0, 1, 5);
+    /// `E9 rel32`
+    pub const JMP: RelativeOperand = RelativeOperand::rel32(0, 1, 5);
+    /// `EB rel8`
+    pub const JMP_SHORT: RelativeOperand = RelativeOperand {
+        instruction_offset: 0,
+        operand_offset: 1,
+        operand_size: 1,
+        instruction_length: 2,
+    };
+    /// `FF 15 rel32`; resolves to the pointer being called through, not the callee.
+    pub const CALL_INDIRECT: RelativeOperand = RelativeOperand::rel32(0, 2, 6);
+
+    /// A 32-bit displacement, as used by `lea`/`mov` with a `[rip+x]` operand.
+    pub const fn rel32(
+        instruction_offset: usize,
+        operand_offset: usize,
+        instruction_length: usize,
+    ) -> RelativeOperand {
+        RelativeOperand {
+            instruction_offset,
+            operand_offset,
+            operand_size: 4,
+            instruction_length,
+        }
+    }
+}
+
+/// A pattern that identifies a location within a module, along with how to get
+/// from the pattern's match to the location of interest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signature {
+    /// The pattern matches the start of the location.
+    Function(&'static str),
+    /// The pattern starts with a `call rel32`; the location is the call's target.
+    RelativeCallsite(&'static str),
+    /// The location is the target of the given operand within the match.
+    Relative(&'static str, RelativeOperand),
+}
+
+impl Signature {
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Signature::Function(pattern) => pattern,
+            Signature::RelativeCallsite(pattern) => pattern,
+            Signature::Relative(pattern, _) => pattern,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use module::{CaptureKind, Pattern};
+
+#[test]
+fn parses_captures() {
+    let pattern = Pattern::parse("48 8B 58 <offset:u8> E8 <target:rel32> ??").unwrap();
+
+    assert_eq!(pattern.len(), 10);
+    let captures: Vec<_> = pattern
+        .captures()
+        .iter()
+        .map(|c| (c.name.as_str(), c.offset, c.kind))
+        .collect();
+    assert_eq!(
+        captures,
+        vec![
+            ("offset", 3, CaptureKind::U8),
+            ("target", 5, CaptureKind::Rel32)
+        ]
+    );
+    assert_eq!(
+        pattern.to_string(),
+        "48 8B 58 <offset:u8> E8 <target:rel32> ?"
+    );
+}
+
+#[test]
+fn rejects_invalid_captures() {
+    assert!(Pattern::parse("48 <offset:u24>").is_err());
+    assert!(Pattern::parse("48 <offset>").is_err());
+    assert!(Pattern::parse("48 <:u8>").is_err());
+    assert!(Pattern::parse("48 <offset:u8").is_err());
+    assert!(Pattern::parse("48 <a:u8> <a:u8>").is_err());
+    assert!(Pattern::parse("<offset:u32>").is_err());
+}
+
+#[test]
+fn extracts_captures() {
+    #[rustfmt::skip]
+    let data = [
+        0x90, 0x90,
+        0x48, 0x8B, 0x58, 0x60,       // mov rbx, [rax+60h]
+        0xE8, 0xF5, 0xFF, 0xFF, 0xFF, // call 0h
+        0xEB, 0x02,                   // jmp 0Fh
+        0x66, 0x0D, 0x34, 0x12,       // or ax, 1234h
+    ];
+    let pattern =
+        Pattern::parse("48 8B 58 <offset:u8> E8 <call:rel32> EB <jump:rel8> 66 0D <immediate:u16>")
+            .unwrap();
+
+    let offset = pattern.scan_first(&data).unwrap();
+    let captures = pattern.captures_at(&data, offset).unwrap();
+    assert_eq!(captures.match_offset, 2);
+    assert_eq!(captures.get("offset").unwrap(), 0x60);
+    assert_eq!(captures.get("call").unwrap(), 0);
+    assert_eq!(captures.get("jump").unwrap(), 0xF);
+    assert_eq!(captures.get("immediate").unwrap(), 0x1234);
+    assert!(captures.get("missing").is_err());
+
+    assert!(pattern.captures_at(&data, 0).is_err());
+}
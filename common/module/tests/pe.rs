@@ -0,0 +1,819 @@
+use module::pe::{Headers, ImportName, ImportedFunction};
+use module::rtti::{self, Vtable};
+use module::{Module, RelativeOperand, Signature};
+
+use std::path::PathBuf;
+
+/// A small DLL laid out by hand, with one of each of the structures we parse. Its
+/// contents are written at their RVAs, and then laid out into a file as the linker would.
+mod fixture {
+    pub const IMAGE_BASE: u64 = 0x1_4000_0000;
+    pub const SIZE_OF_HEADERS: usize = 0x400;
+    pub const SIZE_OF_IMAGE: usize = 0x6000;
+    pub const TIMESTAMP: u32 = 0x6123_4567;
+    pub const CHECKSUM: u32 = 0x00C0_FFEE;
+
+    // (name, rva, virtual size, file offset, file size)
+    pub const SECTIONS: &[(&str, usize, usize, usize, usize)] = &[
+        (".text", 0x1000, 0x100, 0x400, 0x200),
+        (".rdata", 0x2000, 0x400, 0x600, 0x400),
+        (".data", 0x3000, 0x80, 0xA00, 0x200),
+        (".pdata", 0x4000, 0x24, 0xC00, 0x200),
+        (".reloc", 0x5000, 0x1C, 0xE00, 0x200),
+    ];
+
+    pub const PDB_GUID: [u8; 16] = [
+        0x41, 0xE7, 0x66, 0x3F, 0x35, 0x63, 0x3A, 0x43, 0x87, 0xC3, 0xD1, 0x73, 0x2A, 0x8D, 0x20,
+        0x3B,
+    ];
+
+    fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+        buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+    fn put_u16(buf: &mut [u8], offset: usize, value: u16) {
+        put(buf, offset, &value.to_le_bytes());
+    }
+    fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+        put(buf, offset, &value.to_le_bytes());
+    }
+    fn put_u64(buf: &mut [u8], offset: usize, value: u64) {
+        put(buf, offset, &value.to_le_bytes());
+    }
+
+    fn headers(image: &mut [u8]) {
+        put(image, 0, b"MZ");
+        put_u32(image, 0x3C, 0x80);
+        put(image, 0x80, b"PE\0\0");
+
+        let coff = 0x84;
+        put_u16(image, coff, 0x8664);
+        put_u16(image, coff + 2, SECTIONS.len() as u16);
+        put_u32(image, coff + 4, TIMESTAMP);
+        put_u16(image, coff + 16, 240);
+        put_u16(image, coff + 18, 0x2022);
+
+        let optional = coff + 20;
+        put_u16(image, optional, 0x20B);
+        put_u32(image, optional + 16, 0x1000);
+        put_u64(image, optional + 24, IMAGE_BASE);
+        put_u32(image, optional + 32, 0x1000);
+        put_u32(image, optional + 36, 0x200);
+        put_u32(image, optional + 56, SIZE_OF_IMAGE as u32);
+        put_u32(image, optional + 60, SIZE_OF_HEADERS as u32);
+        put_u32(image, optional + 64, CHECKSUM);
+        put_u32(image, optional + 108, 16);
+
+        // (index, rva, size)
+        let directories = [
+            (0, 0x2200, 40),
+            (1, 0x2040, 40),
+            (3, 0x4000, 36),
+            (5, 0x5000, 28),
+            (6, 0x22A0, 28),
+            (9, 0x2300, 40),
+        ];
+        for (index, rva, size) in directories {
+            put_u32(image, optional + 112 + index * 8, rva);
+            put_u32(image, optional + 112 + index * 8 + 4, size);
+        }
+
+        for (i, (name, rva, virtual_size, file_offset, file_size)) in SECTIONS.iter().enumerate() {
+            let section = optional + 240 + i * 40;
+            put(image, section, name.as_bytes());
+            put_u32(image, section + 8, *virtual_size as u32);
+            put_u32(image, section + 12, *rva as u32);
+            put_u32(image, section + 16, *file_size as u32);
+            put_u32(image, section + 20, *file_offset as u32);
+        }
+    }
+
+    fn text(image: &mut [u8]) {
+        #[rustfmt::skip]
+        put(image, 0x1000, &[
+            0x48, 0x83, 0xEC, 0x28,       // sub rsp, 28h
+            0xE8, 0x17, 0x00, 0x00, 0x00, // call 1020h
+            0x48, 0x83, 0xC4, 0x28,       // add rsp, 28h
+            0xC3,                         // ret
+        ]);
+        #[rustfmt::skip]
+        put(image, 0x1020, &[
+            0x48, 0x8D, 0x05, 0xD9, 0x1F, 0x00, 0x00, // lea rax, [rip+1FD9h] (3000h)
+            0xC3,                                     // ret
+        ]);
+        // a chunk of the function at 1000h that has been moved elsewhere
+        put(image, 0x1030, &[0x90, 0x90, 0xC3]);
+        #[rustfmt::skip]
+        put(image, 0x1040, &[
+            0x48, 0xB8,                                     // mov rax, 140003000h
+            0x00, 0x30, 0x00, 0x40, 0x01, 0x00, 0x00, 0x00,
+            0xFF, 0xE0,                                     // jmp rax
+        ]);
+        // more padding than a signature can be made of
+        put(image, 0x1050, &[0x90; 0xB0]);
+    }
+
+    fn rdata(image: &mut [u8]) {
+        // unwind info for 1000h, 1020h, and the chunk at 1030h, chained to 1000h
+        put(
+            image,
+            0x2000,
+            &[0x01, 0x04, 0x01, 0x00, 0x04, 0x42, 0x00, 0x00],
+        );
+        put(image, 0x2010, &[0x01, 0x00, 0x00, 0x00]);
+        put(image, 0x2020, &[0x01 | (0x4 << 3), 0x00, 0x00, 0x00]);
+        put_u32(image, 0x2024, 0x1000);
+        put_u32(image, 0x2028, 0x100E);
+        put_u32(image, 0x202C, 0x2000);
+
+        // imports: one by name, one by ordinal
+        put_u32(image, 0x2040, 0x2100);
+        put_u32(image, 0x2040 + 12, 0x2180);
+        put_u32(image, 0x2040 + 16, 0x3010);
+        for table in [0x2100, 0x3010] {
+            put_u64(image, table, 0x2140);
+            put_u64(image, table + 8, (1 << 63) | 7);
+        }
+        put(image, 0x2142, b"GetProcAddress\0");
+        put(image, 0x2180, b"KERNEL32.dll\0");
+
+        // exports: ordinal 1 is unnamed, ordinal 2 is `helper`
+        put_u32(image, 0x2200 + 12, 0x2280);
+        put_u32(image, 0x2200 + 16, 1);
+        put_u32(image, 0x2200 + 20, 2);
+        put_u32(image, 0x2200 + 24, 1);
+        put_u32(image, 0x2200 + 28, 0x2240);
+        put_u32(image, 0x2200 + 32, 0x2250);
+        put_u32(image, 0x2200 + 36, 0x2258);
+        put_u32(image, 0x2240, 0x1000);
+        put_u32(image, 0x2244, 0x1020);
+        put_u32(image, 0x2250, 0x2260);
+        put_u16(image, 0x2258, 1);
+        put(image, 0x2260, b"helper\0");
+        put(image, 0x2280, b"fixture.dll\0");
+
+        // debug directory with a CodeView record
+        put_u32(image, 0x22A0 + 12, 2);
+        put_u32(image, 0x22A0 + 16, 24 + 12);
+        put_u32(image, 0x22A0 + 20, 0x22C0);
+        put_u32(image, 0x22A0 + 24, 0x6C0);
+        put(image, 0x22C0, b"RSDS");
+        put(image, 0x22C4, &PDB_GUID);
+        put_u32(image, 0x22D4, 3);
+        put(image, 0x22D8, b"fixture.pdb\0");
+
+        // TLS directory
+        put_u64(image, 0x2300, IMAGE_BASE + 0x3020);
+        put_u64(image, 0x2308, IMAGE_BASE + 0x3028);
+        put_u64(image, 0x2310, IMAGE_BASE + 0x3030);
+        put_u64(image, 0x2318, IMAGE_BASE + 0x2340);
+    }
+
+    fn rtti(image: &mut [u8]) {
+        // type descriptor
+        put(image, 0x3050, b".?AVFramework@Framework@System@Client@@\0");
+
+        // complete object locators for the class itself and a base at 10h
+        for (rva, offset) in [(0x2380, 0), (0x2398, 0x10)] {
+            put_u32(image, rva, 1);
+            put_u32(image, rva + 4, offset);
+            put_u32(image, rva + 12, 0x3040);
+            put_u32(image, rva + 20, rva as u32);
+        }
+
+        // vtables, each preceded by their locator
+        put_u64(image, 0x23B0, IMAGE_BASE + 0x2380);
+        put_u64(image, 0x23B8, IMAGE_BASE + 0x1000);
+        put_u64(image, 0x23C0, IMAGE_BASE + 0x1020);
+        put_u64(image, 0x23C8, IMAGE_BASE + 0x2398);
+        put_u64(image, 0x23D0, IMAGE_BASE + 0x1020);
+    }
+
+    fn pdata(image: &mut [u8]) {
+        let functions = [
+            (0x1000, 0x100E, 0x2000),
+            (0x1020, 0x1028, 0x2010),
+            (0x1030, 0x1033, 0x2020),
+        ];
+        for (i, (begin, end, unwind_info)) in functions.iter().enumerate() {
+            put_u32(image, 0x4000 + i * 12, *begin);
+            put_u32(image, 0x4000 + i * 12 + 4, *end);
+            put_u32(image, 0x4000 + i * 12 + 8, *unwind_info);
+        }
+    }
+
+    fn reloc(image: &mut [u8]) {
+        put_u32(image, 0x5000, 0x2000);
+        put_u32(image, 0x5004, 16);
+        for (i, offset) in [0x300u16, 0x308, 0x310, 0x318].iter().enumerate() {
+            put_u16(image, 0x5008 + i * 2, (10 << 12) | offset);
+        }
+        // the `mov` at 1040h, padded with an absolute entry
+        put_u32(image, 0x5010, 0x1000);
+        put_u32(image, 0x5014, 12);
+        put_u16(image, 0x5018, (10 << 12) | 0x42);
+    }
+
+    /// Returns the image as it would be mapped into memory.
+    pub fn image() -> Vec<u8> {
+        let mut image = vec![0u8; SIZE_OF_IMAGE];
+        headers(&mut image);
+        text(&mut image);
+        rdata(&mut image);
+        rtti(&mut image);
+        pdata(&mut image);
+        reloc(&mut image);
+        image
+    }
+
+    /// Returns the image as it would be on disk.
+    pub fn file() -> Vec<u8> {
+        let image = image();
+        let mut file = vec![0u8; 0x1000];
+        file[..SIZE_OF_HEADERS].copy_from_slice(&image[..SIZE_OF_HEADERS]);
+        for (_, rva, virtual_size, file_offset, _) in SECTIONS {
+            file[*file_offset..*file_offset + *virtual_size]
+                .copy_from_slice(&image[*rva..*rva + *virtual_size]);
+        }
+        file
+    }
+}
+
+/// Writes the fixture to a file unique to the calling test, and loads it.
+fn load_fixture(test: &str) -> Module {
+    load_patched_fixture(test, 0, &[])
+}
+
+/// Like `load_fixture`, but with `bytes` written over the section contents at `rva`.
+fn load_patched_fixture(test: &str, rva: usize, bytes: &[u8]) -> Module {
+    let mut file = fixture::file();
+    if let Some((_, section_rva, _, file_offset, _)) = fixture::SECTIONS
+        .iter()
+        .find(|(_, section_rva, size, _, _)| (*section_rva..section_rva + size).contains(&rva))
+    {
+        let offset = file_offset + (rva - section_rva);
+        file[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    let path: PathBuf = std::env::temp_dir().join(format!(
+        "module-fixture-{}-{}.dll",
+        std::process::id(),
+        test
+    ));
+    std::fs::write(&path, file).unwrap();
+    let module = Module::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    module
+}
+
+#[test]
+fn parses_headers() {
+    let headers = Headers::parse(&fixture::file()).unwrap();
+
+    assert_eq!(headers.machine, 0x8664);
+    assert_eq!(headers.timestamp, fixture::TIMESTAMP);
+    assert_eq!(headers.checksum, fixture::CHECKSUM);
+    assert_eq!(headers.image_base, fixture::IMAGE_BASE);
+    assert_eq!(headers.entry_point, 0x1000);
+    assert_eq!(headers.size_of_image as usize, fixture::SIZE_OF_IMAGE);
+    assert_eq!(headers.data_directories.len(), 16);
+
+    let sections: Vec<_> = headers
+        .sections
+        .iter()
+        .map(|s| (s.name.as_str(), s.range()))
+        .collect();
+    assert_eq!(
+        sections,
+        fixture::SECTIONS
+            .iter()
+            .map(|(name, rva, size, _, _)| (*name, *rva..*rva + *size))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rejects_non_pe_files() {
+    assert!(Headers::parse(b"\x7FELF").is_err());
+    assert!(Headers::parse(&[]).is_err());
+
+    let mut file = fixture::file();
+    file[0x84 + 20] = 0x0B;
+    file[0x84 + 21] = 0x01;
+    assert!(Headers::parse(&file).is_err());
+}
+
+#[test]
+fn maps_sections_to_their_rvas() {
+    let module = load_fixture("map");
+    assert_eq!(module.as_bytes(), fixture::image().as_slice());
+    assert_eq!(module.base as u64, fixture::IMAGE_BASE);
+}
+
+#[test]
+fn parses_tls_directory() {
+    let image = fixture::image();
+    let tls = Headers::parse(&image)
+        .unwrap()
+        .tls(&image)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(tls.start_address_of_raw_data, fixture::IMAGE_BASE + 0x3020);
+    assert_eq!(tls.end_address_of_raw_data, fixture::IMAGE_BASE + 0x3028);
+    assert_eq!(tls.address_of_index, fixture::IMAGE_BASE + 0x3030);
+    assert_eq!(tls.address_of_callbacks, fixture::IMAGE_BASE + 0x2340);
+}
+
+#[test]
+fn parses_codeview_record() {
+    let image = fixture::image();
+    let codeview = Headers::parse(&image)
+        .unwrap()
+        .codeview(&image)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(codeview.guid, fixture::PDB_GUID);
+    assert_eq!(codeview.age, 3);
+    assert_eq!(codeview.pdb_path, "fixture.pdb");
+    assert_eq!(codeview.pdb_id(), "3F66E7416335433A87C3D1732A8D203B3");
+}
+
+#[test]
+fn parses_imports() {
+    let image = fixture::image();
+    let imports = Headers::parse(&image).unwrap().imports(&image).unwrap();
+
+    assert_eq!(imports.len(), 1);
+    assert_eq!(imports[0].dll, "KERNEL32.dll");
+    assert_eq!(
+        imports[0].functions,
+        vec![
+            ImportedFunction {
+                name: ImportName::Name("GetProcAddress".to_string()),
+                iat_entry: 0x3010,
+            },
+            ImportedFunction {
+                name: ImportName::Ordinal(7),
+                iat_entry: 0x3018,
+            },
+        ]
+    );
+}
+
+#[test]
+fn parses_exports() {
+    let image = fixture::image();
+    let exports = Headers::parse(&image).unwrap().exports(&image).unwrap();
+
+    let exports: Vec<_> = exports
+        .iter()
+        .map(|e| (e.name.as_deref(), e.ordinal, e.rva))
+        .collect();
+    assert_eq!(
+        exports,
+        vec![(None, 1, 0x1000), (Some("helper"), 2, 0x1020)]
+    );
+}
+
+#[test]
+fn parses_relocations() {
+    let image = fixture::image();
+    let relocations = Headers::parse(&image).unwrap().relocations(&image).unwrap();
+
+    assert_eq!(relocations, vec![0x2300, 0x2308, 0x2310, 0x2318, 0x1042]);
+}
+
+#[test]
+fn finds_function_bounds_from_pdata() {
+    let module = load_fixture("bounds");
+
+    assert_eq!(
+        module.function_bounds(0x1000).unwrap(),
+        Some(0x1000..0x100E)
+    );
+    assert_eq!(
+        module.function_bounds(0x1009).unwrap(),
+        Some(0x1000..0x100E)
+    );
+    assert_eq!(
+        module.function_bounds(0x1027).unwrap(),
+        Some(0x1020..0x1028)
+    );
+    // chained to the function at 1000h
+    assert_eq!(
+        module.function_bounds(0x1031).unwrap(),
+        Some(0x1000..0x100E)
+    );
+    // padding between functions
+    assert_eq!(module.function_bounds(0x1010).unwrap(), None);
+    assert_eq!(module.function_bounds(0x2000).unwrap(), None);
+}
+
+#[test]
+fn restricts_scans_to_sections() {
+    let module = load_fixture("sections");
+
+    assert_eq!(module.section(".pdata"), Some(0x4000..0x4024));
+    assert_eq!(module.section(".bss"), None);
+
+    assert_eq!(
+        module.scan_section(".rdata", "4B 45 52 4E 45 4C").unwrap(),
+        vec![0x2180]
+    );
+    assert!(module
+        .scan_section(".text", "4B 45 52 4E 45 4C")
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        module.scan_section(".text", "C3").unwrap(),
+        vec![0x100D, 0x1027, 0x1032]
+    );
+    assert!(module.scan_section(".bss", "C3").is_err());
+}
+
+#[test]
+fn resolves_signatures_in_code() {
+    let mut module = load_fixture("resolve");
+
+    let function = module
+        .resolve(Signature::Function("48 83 EC 28 E8"))
+        .unwrap();
+    assert_eq!(module.abs_to_rel_addr(function), 0x1000);
+
+    let callee = module
+        .resolve(Signature::RelativeCallsite("E8 ? ? ? ? 48 83 C4 28"))
+        .unwrap();
+    assert_eq!(module.abs_to_rel_addr(callee), 0x1020);
+
+    let global = module
+        .resolve(Signature::Relative(
+            "48 8D 05 ? ? ? ? C3",
+            RelativeOperand::rel32(0, 3, 7),
+        ))
+        .unwrap();
+    assert_eq!(module.abs_to_rel_addr(global), 0x3000);
+}
+
+#[test]
+fn strict_scans_fail_with_every_candidate() {
+    let mut module = load_fixture("strict");
+    module.set_strict(true);
+
+    let error = module.scan("C3").unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "signature is ambiguous, found 3 matches: 100D, 1027, 1032"
+    );
+
+    let function = module.scan("48 83 EC 28").unwrap();
+    assert_eq!(module.abs_to_rel_addr(function), 0x1000);
+}
+
+#[test]
+fn non_strict_scans_take_the_first_match() {
+    let mut module = load_fixture("non_strict");
+
+    let ret = module.scan("C3").unwrap();
+    assert_eq!(module.abs_to_rel_addr(ret), 0x100D);
+}
+
+#[test]
+fn strict_batches_report_ambiguous_signatures() {
+    let mut module = load_fixture("strict_batch");
+    module.set_strict(true);
+
+    let error = module
+        .scan_batch([
+            Signature::Function("C3"),
+            Signature::Function("48 83 EC 28"),
+        ])
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        error,
+        "1 signatures are ambiguous:\nC3 (3 matches: 100D, 1027, 1032)"
+    );
+
+    // the ambiguous signature wasn't cached, so resolving it scans again and fails
+    assert!(module.resolve(Signature::Function("C3")).is_err());
+    let function = module.resolve(Signature::Function("48 83 EC 28")).unwrap();
+    assert_eq!(module.abs_to_rel_addr(function), 0x1000);
+}
+
+/// Writes `cache` to a file unique to the calling test, and reads it into `module`.
+fn read_cache(module: &mut Module, test: &str, cache: serde_json::Value) {
+    let path = std::env::temp_dir().join(format!(
+        "module-fixture-{}-{}.json",
+        std::process::id(),
+        test
+    ));
+    std::fs::write(&path, cache.to_string()).unwrap();
+    module.read_cache(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn fixture_identity() -> serde_json::Value {
+    serde_json::json!({
+        "timestamp": fixture::TIMESTAMP,
+        "checksum": fixture::CHECKSUM,
+        "size_of_image": fixture::SIZE_OF_IMAGE,
+    })
+}
+
+/// Whether the module kept an entry for `pattern` as a hint, which shows up as the
+/// displacement of the candidates found for it.
+fn has_stale_entry(module: &Module, pattern: &'static str) -> bool {
+    module
+        .recover_signature(Signature::Function(pattern), 0)
+        .unwrap()
+        .iter()
+        .any(|c| c.displacement.is_some())
+}
+
+// "C3" matches three times, so a strict module can only resolve it from the cache.
+fn cached_ret(version: u32, identity: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "version": version,
+        "identity": identity,
+        "entries": [[{ "Regular": "C3" }, { "match_offset": 0x1027, "offset": 0x1027 }]],
+    })
+}
+
+#[test]
+fn uses_cache_for_the_same_build() {
+    let mut module = load_fixture("cache_current");
+    module.set_strict(true);
+    read_cache(
+        &mut module,
+        "cache_current",
+        cached_ret(1, fixture_identity()),
+    );
+
+    let ret = module.resolve(Signature::Function("C3")).unwrap();
+    assert_eq!(module.abs_to_rel_addr(ret), 0x1027);
+    assert!(!has_stale_entry(&module, "C3"));
+}
+
+#[test]
+fn keeps_cache_for_another_version_as_hints() {
+    let mut module = load_fixture("cache_version");
+    module.set_strict(true);
+    read_cache(
+        &mut module,
+        "cache_version",
+        cached_ret(0, fixture_identity()),
+    );
+
+    assert!(module.resolve(Signature::Function("C3")).is_err());
+    assert!(has_stale_entry(&module, "C3"));
+}
+
+#[test]
+fn keeps_cache_for_another_build_as_hints() {
+    let mut module = load_fixture("cache_identity");
+    module.set_strict(true);
+    let identity = serde_json::json!({ "timestamp": 0, "checksum": 0, "size_of_image": 0 });
+    read_cache(&mut module, "cache_identity", cached_ret(1, identity));
+
+    assert!(module.resolve(Signature::Function("C3")).is_err());
+    assert!(has_stale_entry(&module, "C3"));
+}
+
+#[test]
+fn keeps_legacy_cache_as_hints() {
+    let mut module = load_fixture("cache_legacy");
+    module.set_strict(true);
+    let cache = serde_json::json!({
+        "entries": [
+            [{ "Regular": "C3" }, 0x1027],
+            // can't be migrated, as the match isn't known
+            [{ "RelativeCallsite": "E8 ? ? ? ? 48 83 C4 28" }, 0x1020],
+        ],
+    });
+    read_cache(&mut module, "cache_legacy", cache);
+
+    assert!(module.resolve(Signature::Function("C3")).is_err());
+    assert!(has_stale_entry(&module, "C3"));
+    assert!(!has_stale_entry(&module, "E8 ? ? ? ? 48 83 C4 28"));
+}
+
+#[test]
+fn rescans_cache_entries_that_fail_verification() {
+    let mut module = load_fixture("cache_verify");
+    let cache = serde_json::json!({
+        "version": 1,
+        "identity": fixture_identity(),
+        "entries": [[{ "Regular": "48 83 EC 28" }, { "match_offset": 0x1020, "offset": 0x1020 }]],
+    });
+    read_cache(&mut module, "cache_verify", cache);
+
+    let function = module.resolve(Signature::Function("48 83 EC 28")).unwrap();
+    assert_eq!(module.abs_to_rel_addr(function), 0x1000);
+    assert!(has_stale_entry(&module, "48 83 EC 28"));
+}
+
+#[test]
+fn resolves_captures() {
+    let mut module = load_fixture("captures");
+
+    let captures = module
+        .resolve_captures(Signature::Function(
+            "48 83 EC <frame:u8> E8 <callee:rel32> 48 83 C4",
+        ))
+        .unwrap();
+    assert_eq!(captures.match_offset, 0x1000);
+    assert_eq!(captures.get("frame").unwrap(), 0x28);
+    assert_eq!(captures.get("callee").unwrap(), 0x1020);
+
+    let function = module.rel_to_abs_addr(0x1000);
+    let captures = module
+        .scan_after_ptr_captures(function, "48 8D 05 <global:rel32> C3")
+        .unwrap();
+    assert_eq!(captures.match_offset, 0x1020);
+    assert_eq!(captures.get("global").unwrap(), 0x3000);
+}
+
+#[test]
+fn recovers_signatures_that_no_longer_match() {
+    let module = load_fixture("recover");
+
+    // two substitutions
+    let candidates = module
+        .recover_signature(
+            Signature::Function("48 83 EC 38 E8 <callee:rel32> 48 83 C4 38 C3"),
+            2,
+        )
+        .unwrap();
+    let best = &candidates[0];
+    assert_eq!(best.offset, 0x1000);
+    assert_eq!(best.distance, 2);
+    assert_eq!(best.similarity, 1.0 - 2.0 / 14.0);
+    assert_eq!(best.displacement, None);
+    assert_eq!(best.pattern, "48 83 EC 28 E8 <callee:rel32> 48 83 C4 28 C3");
+    assert_eq!(best.matches, 1);
+
+    // an inserted byte and a removed byte
+    let candidates = module
+        .recover_signature(Signature::Function("48 83 EC 28 90 E8 ? ? ? ? 48 C4 28"), 2)
+        .unwrap();
+    assert_eq!(candidates[0].offset, 0x1000);
+    assert_eq!(candidates[0].distance, 2);
+    assert_eq!(candidates[0].pattern, "48 83 EC 28 E8 ? ? ? ? 48 83 C4 28");
+
+    assert!(module
+        .recover_signature(Signature::Function("0F 0B 0F 0B 0F 0B 0F 0B"), 2)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn recovers_signatures_near_their_previous_match() {
+    let mut module = load_fixture("recover_previous");
+    let pattern = "48 8D 05 ? ? ? ? C3 90 90 C3";
+
+    // a cache from another build, where the pattern matched 10h further on
+    let cache = serde_json::json!({
+        "version": 1,
+        "identity": { "timestamp": 0, "checksum": 0, "size_of_image": 0 },
+        "entries": [[{ "Regular": pattern }, { "match_offset": 0x1030, "offset": 0x1030 }]],
+    });
+    read_cache(&mut module, "recover_previous", cache);
+
+    // 3 edits away, so only found within the larger budget around the previous match
+    assert!(load_fixture("recover_fresh")
+        .recover_signature(Signature::Function(pattern), 2)
+        .unwrap()
+        .is_empty());
+    let candidates = module
+        .recover_signature(Signature::Function(pattern), 2)
+        .unwrap();
+    assert_eq!(candidates[0].offset, 0x1020);
+    assert_eq!(candidates[0].distance, 3);
+    assert_eq!(candidates[0].displacement, Some(-0x10));
+}
+
+#[test]
+fn generates_signatures_with_wildcarded_operands() {
+    let module = load_fixture("generate_operands");
+
+    // rel32 call target
+    assert_eq!(module.generate_signature(0x1004).unwrap(), "E8");
+    // RIP-relative displacement
+    assert_eq!(module.generate_signature(0x1020).unwrap(), "48 8D 05");
+    // relocated absolute address
+    assert_eq!(module.generate_signature(0x1040).unwrap(), "48 B8");
+}
+
+#[test]
+fn generates_signatures_for_function_starts() {
+    let module = load_fixture("generate_function");
+
+    // a return address in the middle of the function at 1000h
+    assert_eq!(
+        module.generate_function_signature(0x1009).unwrap(),
+        (0x1000, "48 83 EC 28".to_string())
+    );
+    // no bounds for the function at 1040h, so the address itself is used
+    assert_eq!(
+        module.generate_function_signature(0x1040).unwrap(),
+        (0x1040, "48 B8".to_string())
+    );
+}
+
+#[test]
+fn fails_to_generate_signatures_that_are_too_long() {
+    let module = load_fixture("generate_long");
+
+    let error = module.generate_signature(0x1050).unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "failed to find a unique signature for 1050 within 128 bytes"
+    );
+    assert!(module.generate_signature(0x2000).is_err());
+}
+
+#[test]
+fn fails_to_generate_signatures_that_are_not_unique() {
+    // The start of the `lea` at 1020h, right at the end of the code section. The whole
+    // instruction can't fit there, but the signature without its wildcards can.
+    let module = load_patched_fixture("generate_unique", 0x10FD, &[0x48, 0x8D, 0x05]);
+
+    let error = module.generate_signature(0x1020).unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "generated signature `48 8D 05` for 1020 is not unique: [1020, 10FD]"
+    );
+}
+
+#[test]
+fn finds_vtables_by_class_name() {
+    let module = load_fixture("rtti_find");
+    let class_name = "Client::System::Framework::Framework";
+
+    let vtables: Vec<_> = module
+        .find_vtables(class_name)
+        .unwrap()
+        .iter()
+        .map(|v| (v.rva, v.offset))
+        .collect();
+    assert_eq!(vtables, vec![(0x23B8, 0), (0x23D0, 0x10)]);
+
+    let vtable = module.find_vtable(class_name).unwrap();
+    assert_eq!(module.abs_to_rel_addr(vtable), 0x23B8);
+
+    assert!(module.find_vtable("Client::System::Framework").is_err());
+}
+
+#[test]
+fn ignores_class_names_outside_of_type_descriptors() {
+    // too close to the start of .data to be preceded by the rest of a type descriptor
+    let module = load_patched_fixture(
+        "rtti_start",
+        0x3004,
+        b".?AVFramework@Framework@System@Client@@\0",
+    );
+
+    let vtables: Vec<_> = module
+        .find_vtables("Client::System::Framework::Framework")
+        .unwrap()
+        .iter()
+        .map(|v| v.rva)
+        .collect();
+    assert_eq!(vtables, vec![0x23B8, 0x23D0]);
+}
+
+#[test]
+fn identifies_class_of_vtable() {
+    let module = load_fixture("rtti_class");
+
+    assert_eq!(
+        module.vtable_class(module.rel_to_abs_addr(0x23D0)),
+        Some(Vtable {
+            rva: 0x23D0,
+            offset: 0x10,
+            class_name: "Client::System::Framework::Framework".to_string(),
+        })
+    );
+    // not preceded by a locator
+    assert_eq!(module.vtable_class(module.rel_to_abs_addr(0x23C0)), None);
+    assert_eq!(module.vtable_class(module.rel_to_abs_addr(0x1000)), None);
+    assert_eq!(module.vtable_class(std::ptr::null()), None);
+}
+
+#[test]
+fn demangles_class_names() {
+    assert_eq!(
+        rtti::demangle(".?AVFramework@Framework@System@Client@@").as_deref(),
+        Some("Client::System::Framework::Framework")
+    );
+    assert_eq!(
+        rtti::demangle(".?AUtype_info@@").as_deref(),
+        Some("type_info")
+    );
+    assert_eq!(
+        rtti::demangle(".?AV?$vector@HV?$allocator@H@std@@@std@@"),
+        None
+    );
+    assert_eq!(rtti::demangle("Framework"), None);
+}
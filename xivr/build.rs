@@ -30,6 +30,19 @@ fn parsed_const_pair_to_tokens((name, addr): &(String, u64)) -> proc_macro2::Tok
     quote! { pub const #name: usize = #addr; }
 }
 
+/// The functions in `yaml` with their names as they are in data.yml, prefixed with
+/// `prefix` (the class they're in).
+fn yaml_to_symbols(yaml: &yaml::Yaml, prefix: &str) -> Vec<(u64, String)> {
+    yaml_to_hash_iter(yaml)
+        .map(|(addr, name)| {
+            (
+                i64_ea_to_offset(addr.as_i64().unwrap()),
+                format!("{}{}", prefix, name.as_str().unwrap()),
+            )
+        })
+        .collect()
+}
+
 fn yaml_to_constants_tokens(yaml: &yaml::Yaml) -> Vec<proc_macro2::TokenStream> {
     yaml_to_hash_iter(yaml)
         .map(addr_name_pair_parse)
@@ -117,14 +130,35 @@ fn generate_offsets_file(out_dir: &Path) -> anyhow::Result<()> {
     let globals = yaml_to_constants_tokens(&data["globals"]);
     let functions = yaml_to_constants_tokens(&data["functions"]);
 
+    // Every named function, so that addresses can be named at runtime. Virtual functions
+    // are kept as their index into the class' first vtable, as their addresses are only
+    // known once the game is loaded.
+    let mut function_symbols = yaml_to_symbols(&data["functions"], "");
+    let mut vfunc_symbols: Vec<(u64, u64, String)> = vec![];
+
     let mut root_object = Object::Namespace("".to_string(), HashMap::new());
     for (fq_name, data) in yaml_to_hash_iter(&data["classes"]) {
         use convert_case::{Case, Casing};
 
-        let fq_name = fq_name
-            .as_str()
-            .expect("not a string")
-            .replace("Client::", "");
+        let fq_name = fq_name.as_str().expect("not a string");
+        if data["funcs"].as_hash().is_some() {
+            function_symbols.extend(yaml_to_symbols(&data["funcs"], &format!("{}.", fq_name)));
+        }
+        if let (Some(vtbl), Some(vfuncs)) = (
+            data["vtbls"].as_vec().and_then(|v| v.first()),
+            data["vfuncs"].as_hash(),
+        ) {
+            let vtbl = i64_ea_to_offset(vtbl["ea"].as_i64().unwrap());
+            vfunc_symbols.extend(vfuncs.iter().map(|(index, name)| {
+                (
+                    vtbl,
+                    index.as_i64().unwrap() as u64,
+                    format!("{}.{}", fq_name, name.as_str().unwrap()),
+                )
+            }));
+        }
+
+        let fq_name = fq_name.replace("Client::", "");
 
         // yolo we don't support templates
         if fq_name.contains('<') {
@@ -196,6 +230,17 @@ fn generate_offsets_file(out_dir: &Path) -> anyhow::Result<()> {
     }
     let classes = generate_object_token_stream(&root_object);
 
+    function_symbols.sort();
+    function_symbols.dedup_by_key(|(offset, _)| *offset);
+    let function_symbols = function_symbols.iter().map(|(offset, name)| {
+        let offset = *offset as usize;
+        quote! { (#offset, #name) }
+    });
+    let vfunc_symbols = vfunc_symbols.iter().map(|(vtbl, index, name)| {
+        let (vtbl, index) = (*vtbl as usize, *index as usize);
+        quote! { (#vtbl, #index, #name) }
+    });
+
     let tokens = quote! {
         pub const VERSION: &str = #version;
         #[allow(dead_code, non_upper_case_globals, non_snake_case)]
@@ -211,6 +256,13 @@ fn generate_offsets_file(out_dir: &Path) -> anyhow::Result<()> {
             pub mod classes {
                 #classes
             }
+
+            pub mod symbols {
+                /// Offsets of functions from the module base and their names, sorted by offset.
+                pub const FUNCTIONS: &[(usize, &str)] = &[#(#function_symbols,)*];
+                /// Offsets of vtables, indices into them, and the names of the functions there.
+                pub const VFUNCS: &[(usize, usize, &str)] = &[#(#vfunc_symbols,)*];
+            }
         }
     };
 
@@ -0,0 +1,106 @@
+use crate::{Describe, Recorded, SavedCommand, ShaderPayload};
+
+use std::collections::BTreeMap;
+
+/// Which commands to look at. Unset criteria match every command.
+#[derive(Default, Clone)]
+pub struct Filter {
+    /// Matched case-insensitively against `Describe::kind`. Any of them can match; if
+    /// there are none, every kind does.
+    pub kinds: Vec<String>,
+    /// Matched against the command's own address and the addresses its payload refers to.
+    pub address: Option<usize>,
+    pub thread_id: Option<u32>,
+    pub frame: Option<u32>,
+    /// Searched for case-insensitively in the payload's title and details.
+    pub text: Option<String>,
+}
+
+impl Filter {
+    pub fn matches<C: Recorded>(&self, command: &C) -> bool {
+        let payload = command.payload();
+        let kind_matches = self.kinds.is_empty() || {
+            let kind = payload.kind();
+            self.kinds.iter().any(|k| k.eq_ignore_ascii_case(&kind))
+        };
+        let address_matches = self.address.is_none_or(|address| {
+            command.address() == Some(address) || payload.addresses().contains(&address)
+        });
+        let thread_matches = self.thread_id.is_none_or(|id| command.thread_id() == id);
+        let frame_matches = self.frame.is_none_or(|frame| command.frame() == frame);
+        let text_matches = self.text.as_ref().is_none_or(|text| {
+            let text = text.to_lowercase();
+            std::iter::once(payload.title())
+                .chain(payload.details())
+                .any(|t| t.to_lowercase().contains(&text))
+        });
+        kind_matches && address_matches && thread_matches && frame_matches && text_matches
+    }
+}
+
+/// Parses an address as hex, with or without a `0x` prefix.
+pub fn parse_address(address: &str) -> Option<usize> {
+    let digits = address.trim_start_matches("0x").trim_start_matches("0X");
+    usize::from_str_radix(digits, 16).ok()
+}
+
+/// How many of each kind of payload there are in `commands`.
+pub fn count_by_kind<'a, P: Describe + 'a>(
+    commands: impl IntoIterator<Item = &'a SavedCommand<P>>,
+) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for command in commands {
+        *counts.entry(command.payload.kind()).or_default() += 1;
+    }
+    counts
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RenderTargetUsage {
+    /// How many times it was bound by `SetRenderTargets`.
+    pub binds: usize,
+    /// How many draws went to it, whether it was bound or the draw named it directly.
+    pub draws: usize,
+    /// How many times it was the destination of a `CopyTexture`.
+    pub copies: usize,
+}
+
+/// How each texture was used as a render target across `streams`. Each stream starts
+/// with nothing bound, as the game's threads each have their own render targets.
+pub fn render_target_usage<'a>(
+    streams: impl IntoIterator<Item = &'a [SavedCommand<ShaderPayload>]>,
+) -> BTreeMap<usize, RenderTargetUsage> {
+    let mut usage: BTreeMap<usize, RenderTargetUsage> = BTreeMap::new();
+    for stream in streams {
+        let mut bound: Vec<usize> = vec![];
+        for command in stream {
+            match &command.payload {
+                ShaderPayload::SetRenderTargets(rts) => {
+                    bound = rts
+                        .iter()
+                        .map(|rt| rt.0 as usize)
+                        .filter(|&rt| rt != 0)
+                        .collect();
+                    for rt in &bound {
+                        usage.entry(*rt).or_default().binds += 1;
+                    }
+                }
+                ShaderPayload::Draw
+                | ShaderPayload::DrawIndexed
+                | ShaderPayload::DrawIndexedInstanced => {
+                    for rt in &bound {
+                        usage.entry(*rt).or_default().draws += 1;
+                    }
+                }
+                ShaderPayload::UnknownDraw { render_target, .. } => {
+                    usage.entry(render_target.0 as usize).or_default().draws += 1;
+                }
+                ShaderPayload::CopyTexture { dst, .. } => {
+                    usage.entry(dst.0 as usize).or_default().copies += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    usage
+}
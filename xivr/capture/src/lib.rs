@@ -0,0 +1,66 @@
+pub mod analysis;
+mod payload;
+
+pub use payload::*;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Bump this whenever the format changes shape, so that older captures are rejected
+// instead of being misread.
+pub const CAPTURE_VERSION: u32 = 2;
+
+/// A capture of one or more frames, as it's written to disk. Pointers are kept as plain
+/// addresses and callstacks are resolved, so that the capture can be read after the game
+/// has exited.
+#[derive(Serialize, Deserialize)]
+pub struct Capture {
+    pub version: u32,
+    /// The build of the game the capture was taken from; offsets into it are only
+    /// meaningful for that build.
+    pub game: Option<GameBuild>,
+    /// How many frames were captured. Each command records which of them it's from.
+    pub frames: u32,
+    pub shader_streams: BTreeMap<u32, Vec<SavedCommand<ShaderPayload>>>,
+    pub processed_shader_stream: Vec<SavedCommand<ShaderPayload>>,
+    pub d3d_stream: Vec<SavedCommand<D3DPayload>>,
+    pub message_stream: Vec<SavedCommand<MessagePayload>>,
+}
+
+/// Identifies a build of the game by its PE headers. It's written the same way as the
+/// module crate's `ImageIdentity`, which isn't used directly so that reading captures
+/// doesn't need the rest of that crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameBuild {
+    pub timestamp: u32,
+    pub checksum: u32,
+    pub size_of_image: u32,
+}
+
+impl Capture {
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Capture> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let Version { version } = serde_json::from_str(&contents)?;
+        if version != CAPTURE_VERSION {
+            anyhow::bail!(
+                "{} is a version {} capture, but only version {} can be loaded",
+                path.display(),
+                version,
+                CAPTURE_VERSION
+            );
+        }
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
@@ -0,0 +1,163 @@
+use xivr_capture::analysis::{self, Filter};
+use xivr_capture::{Capture, Describe, SavedCommand};
+
+const USAGE: &str =
+    "usage: xivr-capture <path to capture> [--type <payload type>]... [--address <address>] [--thread <thread id>] [--frame <frame>] [--search <text>]";
+
+fn print_counts<P: Describe>(title: &str, commands: &[&SavedCommand<P>]) {
+    if commands.is_empty() {
+        return;
+    }
+
+    println!("  {}:", title);
+    for (kind, count) in analysis::count_by_kind(commands.iter().copied()) {
+        println!("    {:>6} {}", count, kind);
+    }
+}
+
+fn print_stream<P: Describe>(
+    title: &str,
+    commands: &[(usize, &SavedCommand<P>)],
+    group_by_frame: bool,
+) {
+    if commands.is_empty() {
+        return;
+    }
+
+    println!("  {} ({} commands):", title, commands.len());
+    let mut current_frame = None;
+    for (index, command) in commands {
+        if group_by_frame && current_frame != Some(command.frame) {
+            current_frame = Some(command.frame);
+            println!("   frame {}:", command.frame);
+        }
+
+        let address = command
+            .address
+            .map(|a| format!("{:X}", a))
+            .unwrap_or_default();
+        let addresses: Vec<_> = command
+            .payload
+            .addresses()
+            .iter()
+            .map(|a| format!("{:X}", a))
+            .collect();
+        println!(
+            "    #{:<5} {:>9.3}ms {:>16} {}{}",
+            index,
+            command.duration.as_secs_f64() * 1_000.0,
+            address,
+            command.payload.title(),
+            if addresses.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", addresses.join(", "))
+            }
+        );
+    }
+}
+
+fn filtered<'a, P: Describe>(
+    filter: &Filter,
+    commands: &'a [SavedCommand<P>],
+) -> Vec<(usize, &'a SavedCommand<P>)> {
+    commands
+        .iter()
+        .enumerate()
+        .filter(|(_, command)| filter.matches(*command))
+        .collect()
+}
+
+fn without_indices<'a, P>(commands: &[(usize, &'a SavedCommand<P>)]) -> Vec<&'a SavedCommand<P>> {
+    commands.iter().map(|(_, command)| *command).collect()
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().ok_or_else(|| anyhow::anyhow!(USAGE))?;
+
+    let mut filter = Filter::default();
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or_else(|| anyhow::anyhow!(USAGE))?;
+        match arg.as_str() {
+            "--type" => filter.kinds.push(value),
+            "--address" => {
+                filter.address = Some(
+                    analysis::parse_address(&value)
+                        .ok_or_else(|| anyhow::anyhow!("invalid address: {}", value))?,
+                )
+            }
+            "--thread" => filter.thread_id = Some(value.parse()?),
+            "--frame" => filter.frame = Some(value.parse()?),
+            "--search" => filter.text = Some(value),
+            _ => anyhow::bail!(USAGE),
+        }
+    }
+
+    let capture = Capture::load(path.as_ref())?;
+    match capture.game {
+        Some(game) => println!(
+            "capture of game build {:X}/{:X}/{:X}",
+            game.timestamp, game.checksum, game.size_of_image
+        ),
+        None => println!("capture of an unknown game build"),
+    }
+    println!("{} frame(s) captured", capture.frames);
+    let group_by_frame = capture.frames > 1;
+
+    let shader_streams: Vec<_> = capture
+        .shader_streams
+        .iter()
+        .map(|(thread_id, stream)| (thread_id, filtered(&filter, stream)))
+        .collect();
+    let processed_shader_stream = filtered(&filter, &capture.processed_shader_stream);
+    let d3d_stream = filtered(&filter, &capture.d3d_stream);
+    let message_stream = filtered(&filter, &capture.message_stream);
+
+    println!();
+    println!("commands by type:");
+    let all_shader_commands: Vec<_> = shader_streams
+        .iter()
+        .flat_map(|(_, stream)| without_indices(stream))
+        .collect();
+    print_counts("shader", &all_shader_commands);
+    print_counts(
+        "processed shader",
+        &without_indices(&processed_shader_stream),
+    );
+    print_counts("d3d", &without_indices(&d3d_stream));
+    print_counts("messages", &without_indices(&message_stream));
+
+    println!();
+    println!("streams:");
+    for (thread_id, stream) in &shader_streams {
+        print_stream(
+            &format!("shader, thread {}", thread_id),
+            stream,
+            group_by_frame,
+        );
+    }
+    print_stream("processed shader", &processed_shader_stream, group_by_frame);
+    print_stream("d3d", &d3d_stream, group_by_frame);
+    print_stream("messages", &message_stream, group_by_frame);
+
+    println!();
+    println!("render targets:");
+    let usage = analysis::render_target_usage(
+        capture
+            .shader_streams
+            .values()
+            .map(|stream| stream.as_slice()),
+    );
+    for (address, usage) in usage {
+        if filter.address.is_some_and(|a| a != address) {
+            continue;
+        }
+        println!(
+            "  {:>16X}: bound {} times, {} draws, {} copies",
+            address, usage.binds, usage.draws, usage.copies
+        );
+    }
+
+    Ok(())
+}
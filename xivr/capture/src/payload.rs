@@ -0,0 +1,192 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use strum_macros::{Display, EnumCount, EnumDiscriminants};
+
+use std::time::Duration;
+
+/// What the analysis needs to know about a payload, regardless of which stream it's from.
+pub trait Describe {
+    /// The type of the payload, which commands are counted and filtered by.
+    fn kind(&self) -> String;
+    fn title(&self) -> String;
+    /// The addresses of the resources the payload refers to.
+    fn addresses(&self) -> Vec<usize>;
+    /// Any text beyond the title that searches should look through.
+    fn details(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// A recorded command, whether it was loaded from a capture or is still in the debugger.
+pub trait Recorded {
+    type Payload: Describe;
+
+    fn payload(&self) -> &Self::Payload;
+    fn address(&self) -> Option<usize>;
+    fn thread_id(&self) -> u32;
+    fn frame(&self) -> u32;
+}
+
+/// Stands in for the game's texture type, which payloads only ever point at.
+pub enum Texture {}
+
+pub struct Ptr<T>(pub *const T);
+unsafe impl<T> Send for Ptr<T> {}
+unsafe impl<T> Sync for Ptr<T> {}
+impl<T> Copy for Ptr<T> {}
+impl<T> Clone for Ptr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+// Saved as a plain address, which can be shown but not dereferenced once loaded.
+impl<T> Serialize for Ptr<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0 as usize).serialize(serializer)
+    }
+}
+impl<'de, T> Deserialize<'de> for Ptr<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Ptr(usize::deserialize(deserializer)? as *const T))
+    }
+}
+
+#[derive(Display, EnumDiscriminants, EnumCount, Clone, Serialize, Deserialize)]
+pub enum ShaderPayload {
+    SetRenderTargets(Vec<Ptr<Texture>>),
+    SetViewports,
+    SetViewportsFancy,
+    SetScissorRect,
+    Clear,
+    Draw,
+    DrawIndexed,
+    DrawIndexedInstanced,
+    DispatchComputeShader,
+    XIVRHijack,
+    CopyTexture {
+        dst: Ptr<Texture>,
+        src: Ptr<Texture>,
+    },
+    UnknownDraw {
+        render_target: Ptr<Texture>,
+        sampled_texture: Ptr<Texture>,
+    },
+    CopyResource,
+    ResetRendererMaybe,
+    Unknown1,
+    CopySubresourceRegion,
+    SomethingWithStrings,
+    XIVRMarker(String),
+}
+
+impl Describe for ShaderPayload {
+    fn kind(&self) -> String {
+        self.to_string()
+    }
+
+    fn title(&self) -> String {
+        match self {
+            Self::XIVRMarker(s) => s.to_string(),
+            _ => self.to_string(),
+        }
+    }
+
+    fn addresses(&self) -> Vec<usize> {
+        match self {
+            Self::SetRenderTargets(rts) => rts.iter().map(|rt| rt.0 as usize).collect(),
+            Self::CopyTexture { dst, src } => vec![dst.0 as usize, src.0 as usize],
+            Self::UnknownDraw {
+                render_target,
+                sampled_texture,
+            } => vec![render_target.0 as usize, sampled_texture.0 as usize],
+            _ => vec![],
+        }
+    }
+}
+
+/// A call to one of the hooked D3D/DXGI interfaces. The debugger's own payloads hold
+/// live COM pointers, so this only keeps what's shown of them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct D3DPayload {
+    pub interface: String,
+    pub title: String,
+    pub name: String,
+    pub index: usize,
+    pub count: usize,
+    pub arguments: Vec<(String, String)>,
+    pub captures: Vec<(String, String)>,
+    pub resources: Vec<usize>,
+}
+
+impl Describe for D3DPayload {
+    fn kind(&self) -> String {
+        format!("{}::{}", self.interface, self.name)
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn addresses(&self) -> Vec<usize> {
+        self.resources.clone()
+    }
+}
+
+pub type MessagePayload = (String, Vec<String>);
+
+impl Describe for MessagePayload {
+    fn kind(&self) -> String {
+        "Message".to_string()
+    }
+
+    fn title(&self) -> String {
+        self.0.clone()
+    }
+
+    fn addresses(&self) -> Vec<usize> {
+        vec![]
+    }
+
+    fn details(&self) -> Vec<String> {
+        self.1.clone()
+    }
+}
+
+/// A resolved frame of a callstack, which remains meaningful once the process is gone.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Frame {
+    pub module: String,
+    pub offset: usize,
+    pub symbol: Option<String>,
+}
+
+/// A command as it's written to a capture file.
+#[derive(Serialize, Deserialize)]
+pub struct SavedCommand<PayloadType> {
+    pub payload: PayloadType,
+    pub address: Option<usize>,
+    pub callstack: Vec<Frame>,
+    pub thread_id: u32,
+    /// The frame of the capture the command was recorded in, counted from 0.
+    pub frame: u32,
+    pub duration: Duration,
+}
+
+impl<PayloadType: Describe> Recorded for SavedCommand<PayloadType> {
+    type Payload = PayloadType;
+
+    fn payload(&self) -> &PayloadType {
+        &self.payload
+    }
+
+    fn address(&self) -> Option<usize> {
+        self.address
+    }
+
+    fn thread_id(&self) -> u32 {
+        self.thread_id
+    }
+
+    fn frame(&self) -> u32 {
+        self.frame
+    }
+}
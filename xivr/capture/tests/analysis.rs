@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use xivr_capture::analysis::{self, Filter, RenderTargetUsage};
+use xivr_capture::{Capture, Ptr, SavedCommand, ShaderPayload, CAPTURE_VERSION};
+
+fn command(payload: ShaderPayload, address: usize) -> SavedCommand<ShaderPayload> {
+    SavedCommand {
+        payload,
+        address: Some(address),
+        callstack: vec![],
+        thread_id: 1,
+        frame: 0,
+        duration: Duration::ZERO,
+    }
+}
+
+fn texture(address: usize) -> Ptr<xivr_capture::Texture> {
+    Ptr(address as *const _)
+}
+
+fn stream() -> Vec<SavedCommand<ShaderPayload>> {
+    vec![
+        command(
+            ShaderPayload::SetRenderTargets(vec![texture(0xA0), texture(0)]),
+            0x1000,
+        ),
+        command(ShaderPayload::Draw, 0x1010),
+        command(ShaderPayload::DrawIndexed, 0x1020),
+        command(
+            ShaderPayload::CopyTexture {
+                dst: texture(0xB0),
+                src: texture(0xA0),
+            },
+            0x1030,
+        ),
+        command(
+            ShaderPayload::UnknownDraw {
+                render_target: texture(0xB0),
+                sampled_texture: texture(0xA0),
+            },
+            0x1040,
+        ),
+    ]
+}
+
+#[test]
+fn counts_by_kind() {
+    let stream = stream();
+    let counts = analysis::count_by_kind(&stream);
+    assert_eq!(counts.get("Draw"), Some(&1));
+    assert_eq!(counts.get("SetRenderTargets"), Some(&1));
+    assert_eq!(counts.values().sum::<usize>(), stream.len());
+}
+
+#[test]
+fn tracks_render_target_usage() {
+    let stream = stream();
+    let usage = analysis::render_target_usage([stream.as_slice()]);
+    assert_eq!(
+        usage.get(&0xA0),
+        Some(&RenderTargetUsage {
+            binds: 1,
+            draws: 2,
+            copies: 0
+        })
+    );
+    assert_eq!(
+        usage.get(&0xB0),
+        Some(&RenderTargetUsage {
+            binds: 0,
+            draws: 1,
+            copies: 1
+        })
+    );
+    assert!(!usage.contains_key(&0));
+}
+
+#[test]
+fn filters_by_kind_and_address() {
+    let stream = stream();
+    let matching = |filter: Filter| -> Vec<_> {
+        stream
+            .iter()
+            .filter(|c| filter.matches(*c))
+            .map(|c| c.address)
+            .collect()
+    };
+
+    let by_kind = Filter {
+        kinds: vec!["drawindexed".to_string(), "Draw".to_string()],
+        ..Filter::default()
+    };
+    assert_eq!(matching(by_kind), vec![Some(0x1010), Some(0x1020)]);
+
+    let by_text = Filter {
+        text: Some("texture".to_string()),
+        ..Filter::default()
+    };
+    assert_eq!(matching(by_text), vec![Some(0x1030)]);
+
+    let by_texture = Filter {
+        address: Some(0xB0),
+        ..Filter::default()
+    };
+    assert_eq!(matching(by_texture), vec![Some(0x1030), Some(0x1040)]);
+
+    let by_command = Filter {
+        address: Some(0x1010),
+        ..Filter::default()
+    };
+    assert_eq!(matching(by_command), vec![Some(0x1010)]);
+}
+
+#[test]
+fn round_trips_through_a_file() -> anyhow::Result<()> {
+    let capture = Capture {
+        version: CAPTURE_VERSION,
+        game: None,
+        frames: 1,
+        shader_streams: BTreeMap::from([(1, stream())]),
+        processed_shader_stream: vec![],
+        d3d_stream: vec![],
+        message_stream: vec![],
+    };
+
+    let path = std::env::temp_dir().join(format!("xivr-capture-{}.json", std::process::id()));
+    capture.save(&path)?;
+    let loaded = Capture::load(&path);
+    std::fs::remove_file(&path)?;
+    let loaded = loaded?;
+
+    let stream = &loaded.shader_streams[&1];
+    assert_eq!(stream.len(), 5);
+    assert!(matches!(
+        &stream[0].payload,
+        ShaderPayload::SetRenderTargets(rts) if rts[0].0 as usize == 0xA0
+    ));
+    Ok(())
+}
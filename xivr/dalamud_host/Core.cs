@@ -39,8 +39,18 @@ namespace XIVR
         private string ModuleName(string ext) => "xivr_native" + "." + ext;
         private string ModulePath(string ext) => Path.Combine(DirPath, ModuleName(ext));
         private IntPtr module = IntPtr.Zero;
+        private string? modulePath = null;
         private bool visible = true;
 
+        // The copy of the module that's unloading before we load a new one, and how long
+        // we've been waiting for it.
+        private string? reloadingModulePath = null;
+        private Stopwatch reloadTimer = new Stopwatch();
+        private static readonly TimeSpan ReloadTimeout = TimeSpan.FromSeconds(10);
+
+        [DllImport("kernel32.dll", CharSet = CharSet.Unicode)]
+        private static extern IntPtr GetModuleHandleW(string moduleName);
+
         private LogDelegate logDelegate = (s) => PluginLog.Information("native: {0:l}", s);
 
         public Core(DalamudPluginInterface pluginInterface)
@@ -71,6 +81,7 @@ namespace XIVR
                 ((delegate* unmanaged<void>)ModuleFunction("xivr_unload"))();
             }
             this.module = IntPtr.Zero;
+            this.modulePath = null;
 
             onUnload();
         }
@@ -79,7 +90,17 @@ namespace XIVR
         {
             if (this.module != IntPtr.Zero) return;
 
-            this.module = NativeLibrary.Load(ModulePath("dll"));
+            // Load a copy of the module, so that the original can be rebuilt while it's loaded,
+            // and so that reloading loads the new build instead of the one that's unloading.
+            foreach (var stale in Directory.GetFiles(DirPath, "xivr_native.*.dll"))
+            {
+                if (stale == ModulePath("dll")) continue;
+                try { File.Delete(stale); } catch (IOException) { } catch (UnauthorizedAccessException) { }
+            }
+            this.modulePath = Path.Combine(DirPath, string.Format("xivr_native.{0}.dll", DateTime.Now.Ticks));
+            File.Copy(ModulePath("dll"), this.modulePath);
+
+            this.module = NativeLibrary.Load(this.modulePath);
             if (this.module == IntPtr.Zero)
             {
                 throw new Exception(string.Format("Failed to load native module: {0}", Marshal.GetLastWin32Error()));
@@ -110,11 +131,42 @@ namespace XIVR
             }
         }
 
+        // The native module unloads itself once no thread is inside its hooks, which may not be
+        // until after this frame, and saves its state for the next one to pick up when it does.
+        private void Reload()
+        {
+            this.reloadingModulePath = this.modulePath;
+            Unload(() => { });
+            this.reloadTimer.Restart();
+        }
+
+        private void UpdateReload()
+        {
+            if (this.reloadingModulePath == null) return;
+
+            if (GetModuleHandleW(this.reloadingModulePath) == IntPtr.Zero)
+            {
+                this.reloadingModulePath = null;
+                Load();
+            }
+            else if (this.reloadTimer.Elapsed > ReloadTimeout)
+            {
+                PluginLog.Error("Native module did not unload, not reloading");
+                this.reloadingModulePath = null;
+            }
+        }
+
         private void OnDraw()
         {
+            UpdateReload();
+
             if (ImGui.Begin("XIVR Loader", ref this.visible, ImGuiWindowFlags.NoScrollbar | ImGuiWindowFlags.NoScrollWithMouse))
             {
-                if (this.module == IntPtr.Zero)
+                if (this.reloadingModulePath != null)
+                {
+                    ImGui.Text("Reloading...");
+                }
+                else if (this.module == IntPtr.Zero)
                 {
                     if (ImGui.Button("Load"))
                     {
@@ -127,6 +179,11 @@ namespace XIVR
                     {
                         Unload(() => { });
                     }
+                    ImGui.SameLine();
+                    if (ImGui.Button("Reload"))
+                    {
+                        Reload();
+                    }
                 }
             }
 
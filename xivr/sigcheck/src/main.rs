@@ -0,0 +1,93 @@
+#[path = "../../src/signatures.rs"]
+mod signatures;
+
+use module::{Module, Pattern, Signature};
+
+// How many byte edits away from a failed signature to look for candidates.
+const RECOVERY_MAX_DISTANCE: usize = 4;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().ok_or_else(|| {
+        anyhow::anyhow!("usage: sigcheck <path to ffxiv_dx11.exe> [path to previous cache]")
+    })?;
+
+    let mut module = Module::from_file(path.as_ref())?;
+    module.set_strict(true);
+    if let Some(cache_path) = args.next() {
+        module.read_cache(cache_path.as_ref())?;
+    }
+    // Ambiguous signatures are reported here, and then again with the rest of the failures.
+    if let Err(e) = module.scan_batch(signatures::ALL.iter().map(|(_, signature)| *signature)) {
+        println!("{}", e);
+    }
+
+    let mut failures = 0;
+    for (name, signature) in signatures::ALL {
+        match module.resolve(*signature) {
+            Ok(address) => {
+                println!("ok     {}: {:X}", name, module.abs_to_rel_addr(address));
+                if let Err(e) = print_captures(&mut module, *signature) {
+                    println!("         failed to resolve captures: {}", e);
+                    failures += 1;
+                }
+            }
+            Err(e) => {
+                println!("failed {}: {} ({})", name, e, signature.pattern());
+                failures += 1;
+
+                let candidates = match module.recover_signature(*signature, RECOVERY_MAX_DISTANCE) {
+                    Ok(candidates) => candidates,
+                    Err(e) => {
+                        println!("         failed to look for candidates: {}", e);
+                        continue;
+                    }
+                };
+                for candidate in candidates {
+                    println!(
+                        "         candidate {:X} ({:.0}% similar, {} edits{}{}): {}",
+                        candidate.offset,
+                        candidate.similarity * 100.0,
+                        candidate.distance,
+                        candidate
+                            .displacement
+                            .map(|d| format!(
+                                ", {}{:X} from previous",
+                                if d < 0 { "-" } else { "+" },
+                                d.unsigned_abs()
+                            ))
+                            .unwrap_or_default(),
+                        if candidate.matches == 1 {
+                            String::new()
+                        } else {
+                            format!(", matches {} times", candidate.matches)
+                        },
+                        candidate.pattern
+                    );
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!(
+            "{} of {} signatures failed",
+            failures,
+            signatures::ALL.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_captures(module: &mut Module, signature: Signature) -> anyhow::Result<()> {
+    let captures = module.resolve_captures(signature)?;
+    for capture in Pattern::parse(signature.pattern())?.captures() {
+        println!(
+            "         {}: {:X}",
+            capture.name,
+            captures.get(&capture.name)?
+        );
+    }
+    Ok(())
+}
@@ -2,11 +2,20 @@ pub mod rendering {
     pub const DISABLE_GAME: bool = false;
     pub const DISABLE_UI: bool = true;
 
-    pub const CAPTURE_D3D_COMMANDS: bool = false;
-
     pub const SHADER_COMMAND_HIJACKED_TYPE: usize = 9;
 }
 
+pub mod hooks {
+    // fail tier1 load if any signature matches more than once, instead of hooking the first match
+    pub const STRICT_SIGNATURES: bool = true;
+    // how many byte edits away from a signature that fails to resolve to look for where it went
+    pub const SIGNATURE_RECOVERY_MAX_DISTANCE: usize = 4;
+    // how long unloading waits for threads to leave our hooks before giving up on them
+    pub const UNLOAD_TIMEOUT_MS: u64 = 2000;
+    // how long unloading keeps waiting after they've left, for the code around the hooks
+    pub const UNLOAD_GRACE_PERIOD_MS: u64 = 50;
+}
+
 pub mod xr {
     // temporary settings while I fix other code
     pub const CHANGE_WINDOW_SIZE: bool = true;
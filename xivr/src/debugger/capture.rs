@@ -0,0 +1,39 @@
+use crate::util;
+
+use xivr_capture::Capture;
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn directory() -> anyhow::Result<PathBuf> {
+    Ok(util::this_module_directory()?.join("captures"))
+}
+
+/// Writes `capture` to a new file in the captures directory, returning its path.
+pub fn save(capture: &Capture) -> anyhow::Result<PathBuf> {
+    let directory = directory()?;
+    std::fs::create_dir_all(&directory)?;
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let path = directory.join(format!("capture-{}.json", timestamp));
+    capture.save(&path)?;
+    Ok(path)
+}
+
+/// The captures in the captures directory, oldest first.
+pub fn list() -> anyhow::Result<Vec<PathBuf>> {
+    let directory = directory()?;
+    if !directory.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut paths = vec![];
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.extension().map_or(false, |e| e == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
@@ -1,5 +1,4 @@
-use crate::ct_config;
-
+use crate::debugger::capture;
 use crate::debugger::d3d_payload::*;
 use crate::debugger::message_payload::*;
 use crate::debugger::payload::*;
@@ -7,19 +6,133 @@ use crate::debugger::shader_payload::*;
 
 use crate::game::graphics::kernel;
 use crate::game::graphics::kernel::ShaderCommandType;
-use crate::module::Module;
+use crate::util;
+use crate::util::symbols::Symbolizer;
+use xivr_capture::analysis::{self, Filter};
+use xivr_capture::{Capture, GameBuild, CAPTURE_VERSION};
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 use std::string::ToString;
 use std::time::Instant;
 
 use cimgui as ig;
 
-const FRAMES_TO_CAPTURE: u32 = 1;
+const DEFAULT_FRAMES_TO_CAPTURE: i32 = 1;
+const MAX_FRAMES_TO_CAPTURE: i32 = 60;
 
 struct Stream<CommandType> {
     stream: Vec<CommandType>,
     selected_index: Option<usize>,
+    filter: StreamFilter,
+}
+
+/// What's been entered into a stream's filter controls.
+struct StreamFilter {
+    kinds: BTreeSet<String>,
+    thread_id: String,
+    texture: String,
+    text: String,
+    only_show_matches: bool,
+    /// Set when the selection was moved to another match, so that the list scrolls to it.
+    scroll_to_selected: bool,
+}
+
+impl Default for StreamFilter {
+    fn default() -> Self {
+        StreamFilter {
+            kinds: BTreeSet::new(),
+            thread_id: String::new(),
+            texture: String::new(),
+            text: String::new(),
+            only_show_matches: true,
+            scroll_to_selected: false,
+        }
+    }
+}
+
+impl StreamFilter {
+    /// Fields that can't be parsed are left out of the filter, instead of matching nothing.
+    fn to_filter(&self) -> Filter {
+        Filter {
+            kinds: self.kinds.iter().cloned().collect(),
+            address: analysis::parse_address(self.texture.trim()),
+            thread_id: self.thread_id.trim().parse().ok(),
+            frame: None,
+            text: Some(self.text.trim())
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string()),
+        }
+    }
+
+    fn draw<PayloadType: Describe>(
+        &mut self,
+        title: &str,
+        stream: &[Command<PayloadType>],
+    ) -> anyhow::Result<()> {
+        if !ig::collapsing_header(&format!("Filter##{}", title), None, None)? {
+            return Ok(());
+        }
+
+        ig::input_text(&format!("Search##{}", title), &mut self.text, 256, None)?;
+        ig::input_text(
+            &format!("Thread ID##{}", title),
+            &mut self.thread_id,
+            16,
+            Some(ig::InputTextFlags::CharsDecimal),
+        )?;
+        ig::input_text(
+            &format!("Texture##{}", title),
+            &mut self.texture,
+            32,
+            Some(ig::InputTextFlags::CharsHexadecimal),
+        )?;
+        ig::checkbox(
+            &format!("Only show matches##{}", title),
+            &mut self.only_show_matches,
+        )?;
+
+        ig::text("Types:");
+        let kinds: BTreeSet<String> = stream.iter().map(|cmd| cmd.payload.kind()).collect();
+        for kind in kinds {
+            let mut selected = self.kinds.contains(&kind);
+            if ig::checkbox(&format!("{}##{}", kind, title), &mut selected)? {
+                if selected {
+                    self.kinds.insert(kind);
+                } else {
+                    self.kinds.remove(&kind);
+                }
+            }
+        }
+        if !self.kinds.is_empty() && ig::small_button(&format!("Clear types##{}", title))? {
+            self.kinds.clear();
+        }
+
+        Ok(())
+    }
+}
+
+impl<PayloadType: Clone> Stream<Command<PayloadType>> {
+    fn to_saved<SavedPayloadType: From<PayloadType>>(
+        &self,
+        symbolizer: &mut Symbolizer,
+    ) -> Vec<SavedCommand<SavedPayloadType>> {
+        self.stream
+            .iter()
+            .map(|cmd| cmd.to_saved(symbolizer))
+            .collect()
+    }
+}
+
+impl<PayloadType, SavedPayloadType: Into<PayloadType>> From<Vec<SavedCommand<SavedPayloadType>>>
+    for Stream<Command<PayloadType>>
+{
+    fn from(val: Vec<SavedCommand<SavedPayloadType>>) -> Self {
+        val.into_iter()
+            .map(Command::from)
+            .collect::<Vec<_>>()
+            .into()
+    }
 }
 
 impl<CommandType> From<Vec<CommandType>> for Stream<CommandType> {
@@ -27,19 +140,24 @@ impl<CommandType> From<Vec<CommandType>> for Stream<CommandType> {
         Stream {
             stream: val,
             selected_index: None,
+            filter: StreamFilter::default(),
         }
     }
 }
 
 enum CommandStreamState {
     Uncaptured,
-    WantToCapture,
+    WantToCapture {
+        frames: u32,
+    },
     Capturing {
         start_instant: Instant,
         shader_stream: Vec<ShaderCommand>,
         processed_shader_stream: Vec<ShaderCommand>,
         d3d_stream: Vec<D3DCommand>,
         message_stream: Vec<MessageCommand>,
+        /// The frame being captured, counted in `Framework` ticks from the start.
+        frame: u32,
         frames: u32,
     },
     Captured {
@@ -47,47 +165,95 @@ enum CommandStreamState {
         processed_shader_stream: Stream<ShaderCommand>,
         d3d_stream: Stream<D3DCommand>,
         message_stream: Stream<MessageCommand>,
+        frames: u32,
+        /// Where the capture was loaded from, if it wasn't captured in this session.
+        loaded_from: Option<PathBuf>,
     },
 }
+
+/// The build of the game we're running in, as captures record it.
+fn game_build() -> Option<GameBuild> {
+    let identity = util::game_module_mut().and_then(|m| m.identity()).ok()?;
+    Some(GameBuild {
+        timestamp: identity.timestamp,
+        checksum: identity.checksum,
+        size_of_image: identity.size_of_image,
+    })
+}
+
+impl CommandStreamState {
+    fn to_capture(&self, symbolizer: &mut Symbolizer) -> Option<Capture> {
+        match self {
+            CommandStreamState::Captured {
+                shader_streams,
+                processed_shader_stream,
+                d3d_stream,
+                message_stream,
+                frames,
+                ..
+            } => Some(Capture {
+                version: CAPTURE_VERSION,
+                frames: *frames,
+                game: game_build(),
+                shader_streams: shader_streams
+                    .iter()
+                    .map(|(thread_id, stream)| (*thread_id, stream.to_saved(symbolizer)))
+                    .collect(),
+                processed_shader_stream: processed_shader_stream.to_saved(symbolizer),
+                d3d_stream: d3d_stream.to_saved(symbolizer),
+                message_stream: message_stream.to_saved(symbolizer),
+            }),
+            _ => None,
+        }
+    }
+
+    fn from_capture(capture: Capture, path: PathBuf) -> CommandStreamState {
+        CommandStreamState::Captured {
+            shader_streams: capture
+                .shader_streams
+                .into_iter()
+                .map(|(thread_id, stream)| (thread_id, stream.into()))
+                .collect(),
+            processed_shader_stream: capture.processed_shader_stream.into(),
+            d3d_stream: capture.d3d_stream.into(),
+            message_stream: capture.message_stream.into(),
+            frames: capture.frames,
+            loaded_from: Some(path),
+        }
+    }
+}
 struct CommandStreamUI {
-    module_name_lookup: HashMap<*const u8, String>,
+    symbolizer: Symbolizer,
     selected_cmd_address: Option<*const kernel::ShaderCommand>,
+    generated_signature: Option<(usize, Result<String, String>)>,
+    frames_to_capture: i32,
+    saved_captures: Option<Vec<PathBuf>>,
+    capture_file_status: Option<Result<String, String>>,
 }
 impl CommandStreamUI {
     pub fn new() -> CommandStreamUI {
-        let module_name_lookup: HashMap<_, _> = Module::get_all()
-            .iter()
-            .map(|m| {
-                (
-                    m.base as *const u8,
-                    m.filename().unwrap_or_else(|| "unknown".to_string()),
-                )
-            })
-            .collect();
-
         CommandStreamUI {
-            module_name_lookup,
+            symbolizer: Symbolizer::new(),
             selected_cmd_address: None,
+            generated_signature: None,
+            frames_to_capture: DEFAULT_FRAMES_TO_CAPTURE,
+            saved_captures: None,
+            capture_file_status: None,
         }
     }
 
-    fn module_name_from_mba(&self, mba: *const u8) -> String {
-        self.module_name_lookup
-            .get(&mba)
-            .cloned()
-            .unwrap_or(format!("{:X?}", mba))
-    }
-
     fn draw_cmd<PayloadType: Payload>(
         &mut self,
-        index: usize,
+        index: Option<usize>,
         cmd: &Command<PayloadType>,
     ) -> anyhow::Result<()> {
         ig::begin_group();
         if ig::begin_child("Item view", Some(ig::Vec2::new(0.0, -1.0)), None, None)? {
             {
-                ig::same_line(Some(0.0), Some(0.0));
-                ig::textf!("#{}", index);
+                if let Some(index) = index {
+                    ig::same_line(Some(0.0), Some(0.0));
+                    ig::textf!("#{}", index);
+                }
 
                 ig::same_line(Some(0.0), Some(4.0));
                 ig::push_style_color(ig::Col::Text, cmd.payload.colour());
@@ -98,6 +264,9 @@ impl CommandStreamUI {
             ig::separator();
             {
                 ig::textf!("Thread ID: {}", cmd.thread_id);
+                if index.is_some() {
+                    ig::textf!("Frame: {}", cmd.frame);
+                }
                 if let Some(address) = cmd.address {
                     ig::text("Pointer: ");
                     ig::same_line(None, Some(0.0));
@@ -110,37 +279,105 @@ impl CommandStreamUI {
 
             ig::separator();
             if ig::collapsing_header("Data", None, None)? {
-                cmd.payload.draw()?;
+                cmd.payload
+                    .draw(matches!(cmd.callstack, Callstack::Saved(_)))?;
             }
 
-            #[allow(clippy::collapsible_if)]
             if ig::collapsing_header("Callstack", None, None)? {
-                if ig::begin_table("xivr_debugger_callstack", 2, None, None, None)? {
-                    ig::table_setup_column("Module", None, None, None)?;
-                    ig::table_setup_column("Address", None, None, None)?;
-                    ig::table_headers_row();
+                match &cmd.callstack {
+                    Callstack::Live(backtrace) => self.draw_live_callstack(backtrace)?,
+                    Callstack::Saved(frames) => Self::draw_saved_callstack(frames)?,
+                }
+            }
+            ig::end_child();
+        }
+        ig::end_group();
 
-                    for frame in cmd.backtrace.frames().iter().skip(5) {
-                        let mba = frame.module_base_address().unwrap_or(std::ptr::null_mut());
-                        let address = unsafe { frame.ip().offset_from(mba) };
+        Ok(())
+    }
 
-                        ig::table_next_row(None, None);
-                        {
-                            ig::table_next_column();
-                            ig::text(&self.module_name_from_mba(mba as *const _));
-                            ig::table_next_column();
-                            let addr_str = format!("0x{:0width$X}", address, width = 6);
-                            if ig::small_button(&addr_str)? {
-                                ig::set_clipboard_text(&addr_str)?;
-                            }
+    fn draw_live_callstack(&mut self, backtrace: &backtrace::Backtrace) -> anyhow::Result<()> {
+        let game_module = util::game_module_mut()?;
+        if ig::begin_table("xivr_debugger_callstack", 4, None, None, None)? {
+            ig::table_setup_column("Module", None, None, None)?;
+            ig::table_setup_column("Address", None, None, None)?;
+            ig::table_setup_column("Symbol", None, None, None)?;
+            ig::table_setup_column("Signature", None, None, None)?;
+            ig::table_headers_row();
+
+            for frame in backtrace.frames().iter().skip(Callstack::RECORDING_FRAMES) {
+                let mba = frame.module_base_address().unwrap_or(std::ptr::null_mut());
+                let address = unsafe { frame.ip().offset_from(mba) };
+
+                ig::table_next_row(None, None);
+                {
+                    ig::table_next_column();
+                    ig::text(&self.symbolizer.module_name(mba as *const _));
+                    ig::table_next_column();
+                    let addr_str = format!("0x{:0width$X}", address, width = 6);
+                    if ig::small_button(&addr_str)? {
+                        ig::set_clipboard_text(&addr_str)?;
+                    }
+                    ig::table_next_column();
+                    // Looked up once per address, as this is drawn every frame.
+                    ig::text(&self.symbolizer.symbol(frame).unwrap_or_default());
+                    ig::table_next_column();
+                    if mba as *mut u8 == game_module.base
+                        && ig::small_button(&format!("Generate##{}", addr_str))?
+                    {
+                        let (function, signature) =
+                            match game_module.generate_function_signature(address as usize) {
+                                Ok((function, signature)) => (function, Ok(signature)),
+                                Err(e) => (address as usize, Err(e.to_string())),
+                            };
+                        if let Ok(signature) = &signature {
+                            ig::set_clipboard_text(signature)?;
                         }
+                        self.generated_signature = Some((function, signature));
                     }
-                    ig::end_table();
                 }
             }
-            ig::end_child();
+            ig::end_table();
+        }
+
+        match &self.generated_signature {
+            Some((address, Ok(signature))) => {
+                ig::textf!("Signature for 0x{:X} (copied): {}", address, signature)
+            }
+            Some((address, Err(error))) => {
+                ig::textf!(
+                    "Failed to generate signature for 0x{:X}: {}",
+                    address,
+                    error
+                )
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn draw_saved_callstack(frames: &[Frame]) -> anyhow::Result<()> {
+        if ig::begin_table("xivr_debugger_callstack", 3, None, None, None)? {
+            ig::table_setup_column("Module", None, None, None)?;
+            ig::table_setup_column("Address", None, None, None)?;
+            ig::table_setup_column("Symbol", None, None, None)?;
+            ig::table_headers_row();
+
+            for frame in frames {
+                ig::table_next_row(None, None);
+                ig::table_next_column();
+                ig::text(&frame.module);
+                ig::table_next_column();
+                let addr_str = format!("0x{:0width$X}", frame.offset, width = 6);
+                if ig::small_button(&addr_str)? {
+                    ig::set_clipboard_text(&addr_str)?;
+                }
+                ig::table_next_column();
+                ig::text(frame.symbol.as_deref().unwrap_or(""));
+            }
+            ig::end_table();
         }
-        ig::end_group();
 
         Ok(())
     }
@@ -150,6 +387,7 @@ impl CommandStreamUI {
         title: &str,
         stream: &mut Stream<Command<PayloadType>>,
         select: bool,
+        group_by_frame: bool,
     ) -> anyhow::Result<()> {
         if ig::begin_tab_item(
             title,
@@ -160,17 +398,81 @@ impl CommandStreamUI {
                 None
             },
         )? {
+            stream.filter.draw(title, &stream.stream)?;
+
+            let filter = stream.filter.to_filter();
+            let matches: Vec<usize> = stream
+                .stream
+                .iter()
+                .enumerate()
+                .filter(|(_, cmd)| filter.matches(*cmd))
+                .map(|(i, _)| i)
+                .collect();
+
+            if ig::small_button(&format!("Previous match##{}", title))? {
+                let previous = match stream.selected_index {
+                    Some(selected) => matches.iter().rev().find(|i| **i < selected),
+                    None => None,
+                };
+                if let Some(index) = previous.or_else(|| matches.last()) {
+                    stream.selected_index = Some(*index);
+                    stream.filter.scroll_to_selected = true;
+                }
+            }
+            ig::same_line(None, None);
+            if ig::small_button(&format!("Next match##{}", title))? {
+                let next = match stream.selected_index {
+                    Some(selected) => matches.iter().find(|i| **i > selected),
+                    None => None,
+                };
+                if let Some(index) = next.or_else(|| matches.first()) {
+                    stream.selected_index = Some(*index);
+                    stream.filter.scroll_to_selected = true;
+                }
+            }
+            ig::same_line(None, None);
+            ig::textf!(
+                "{} of {} commands match",
+                matches.len(),
+                stream.stream.len()
+            );
+
             if ig::begin_child(
                 &format!("Command Stream ({})", title),
                 Some(ig::Vec2::new(300.0, 0.0)),
                 Some(true),
                 None,
             )? {
+                let mut current_frame = None;
+                let mut frame_open = true;
+                let mut matches = matches.iter().peekable();
                 for (i, cmd) in stream.stream.iter().enumerate() {
+                    let is_match = matches.next_if(|m| **m == i).is_some();
+                    if !is_match && stream.filter.only_show_matches {
+                        continue;
+                    }
+
+                    if group_by_frame && current_frame != Some(cmd.frame) {
+                        current_frame = Some(cmd.frame);
+                        frame_open = ig::collapsing_header(
+                            &format!("Frame {}##{}", cmd.frame, title),
+                            None,
+                            Some(ig::TreeNodeFlags::DefaultOpen),
+                        )?;
+                    }
+                    if !frame_open {
+                        continue;
+                    }
+
                     let is_selected = stream.selected_index == Some(i);
                     let name = format!("{}: {}", i, cmd.payload.title());
 
-                    ig::push_style_color(ig::Col::Text, cmd.payload.colour());
+                    let colour = if is_match {
+                        cmd.payload.colour()
+                    } else {
+                        ig::Color::new(0.5, 0.5, 0.5, 1.0)
+                    };
+                    ig::push_style_color(ig::Col::Text, colour);
                     if ig::selectable(&name, Some(is_selected), None, None)? {
                         stream.selected_index = Some(i);
                     }
@@ -178,6 +480,10 @@ impl CommandStreamUI {
 
                     if is_selected {
                         ig::set_item_default_focus();
+                        if stream.filter.scroll_to_selected {
+                            ig::set_scroll_here_y(None);
+                            stream.filter.scroll_to_selected = false;
+                        }
                     }
                 }
                 ig::end_child();
@@ -186,7 +492,7 @@ impl CommandStreamUI {
             ig::same_line(None, None);
             if let Some(index) = stream.selected_index {
                 let cmd = &stream.stream[index];
-                self.draw_cmd(index, cmd)?;
+                self.draw_cmd(Some(index), cmd)?;
             }
             ig::end_tab_item();
         }
@@ -200,8 +506,15 @@ impl CommandStreamUI {
             processed_shader_stream,
             d3d_stream,
             message_stream,
+            frames,
+            loaded_from,
         } = state
         {
+            let group_by_frame = *frames > 1;
+            if let Some(path) = loaded_from {
+                ig::textf!("Loaded from {} (read-only)", path.display());
+            }
+
             if ig::begin_tab_bar("xivr_debugger_command_stream_tabs", None)? {
                 let mut selected_thread_id = None;
                 for (thread_id, shader_stream) in shader_streams.iter_mut() {
@@ -238,17 +551,23 @@ impl CommandStreamUI {
                                 &format!("{}", thread_id),
                                 shader_stream,
                                 is_selected,
+                                group_by_frame,
                             )?;
                         }
                         ig::end_tab_bar();
                     }
                     ig::end_tab_item();
                 }
-                self.draw_stream("Game (Processed)", processed_shader_stream, false)?;
-                if ct_config::rendering::CAPTURE_D3D_COMMANDS {
-                    self.draw_stream("D3D", d3d_stream, false)?;
+                self.draw_stream(
+                    "Game (Processed)",
+                    processed_shader_stream,
+                    false,
+                    group_by_frame,
+                )?;
+                if !d3d_stream.stream.is_empty() {
+                    self.draw_stream("D3D", d3d_stream, false, group_by_frame)?;
                 }
-                self.draw_stream("Messages", message_stream, false)?;
+                self.draw_stream("Messages", message_stream, false, group_by_frame)?;
                 ig::end_tab_bar();
             }
         }
@@ -259,15 +578,87 @@ impl CommandStreamUI {
     fn draw(&mut self, state: &mut CommandStreamState) -> anyhow::Result<()> {
         {
             if ig::button("Capture", None)? {
-                *state = CommandStreamState::WantToCapture;
+                *state = CommandStreamState::WantToCapture {
+                    frames: self.frames_to_capture as u32,
+                };
+            }
+            ig::same_line(None, None);
+            ig::set_next_item_width(100.0);
+            if ig::input_int("Frames", &mut self.frames_to_capture, None, None, None)? {
+                self.frames_to_capture = self.frames_to_capture.clamp(1, MAX_FRAMES_TO_CAPTURE);
+            }
+
+            if let CommandStreamState::Captured {
+                loaded_from: None, ..
+            } = state
+            {
+                ig::same_line(None, None);
+                if ig::button("Save capture", None)? {
+                    self.capture_file_status = Some(
+                        state
+                            .to_capture(&mut self.symbolizer)
+                            .map_or(Ok(None), |c| capture::save(&c).map(Some))
+                            .map(|p| match p {
+                                Some(p) => format!("Saved capture to {}", p.display()),
+                                None => "Nothing to save".to_string(),
+                            })
+                            .map_err(|e| e.to_string()),
+                    );
+                }
+            }
+
+            ig::same_line(None, None);
+            if ig::button("Load capture", None)? {
+                self.saved_captures = match capture::list() {
+                    Ok(paths) => Some(paths),
+                    Err(e) => {
+                        self.capture_file_status = Some(Err(e.to_string()));
+                        None
+                    }
+                };
+            }
+        }
+
+        if let Some(paths) = &self.saved_captures {
+            let mut selected_path = None;
+            if paths.is_empty() {
+                ig::text("No saved captures.");
+            }
+            for path in paths {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                if ig::selectable(&name, None, None, None)? {
+                    selected_path = Some(path.clone());
+                }
+            }
+
+            if let Some(path) = selected_path {
+                self.saved_captures = None;
+                self.capture_file_status = match Capture::load(&path) {
+                    Ok(capture) => {
+                        let status = if capture.game.is_some() && capture.game != game_build() {
+                            "Loaded a capture from a different build of the game"
+                        } else {
+                            "Loaded capture"
+                        };
+                        *state = CommandStreamState::from_capture(capture, path);
+                        Some(Ok(status.to_string()))
+                    }
+                    Err(e) => Some(Err(format!("Failed to load capture: {}", e))),
+                };
             }
         }
 
+        match &self.capture_file_status {
+            Some(Ok(status)) => ig::text(status),
+            Some(Err(error)) => ig::textf!("Error: {}", error),
+            None => {}
+        }
+
         if let CommandStreamState::Captured { .. } = state {
             self.draw_captured(state)?;
         } else {
             ig::separator();
-            ig::text("Capture a frame to proceed.");
+            ig::text("Capture one or more frames to proceed.");
         }
 
         Ok(())
@@ -288,10 +679,14 @@ impl CommandStream {
 
     pub fn pre_update(&mut self) -> anyhow::Result<()> {
         match self.state {
-            CommandStreamState::WantToCapture => self.start_capture()?,
-            CommandStreamState::Capturing { ref mut frames, .. } => {
-                *frames += 1;
-                if *frames == FRAMES_TO_CAPTURE {
+            CommandStreamState::WantToCapture { frames } => self.start_capture(frames)?,
+            CommandStreamState::Capturing {
+                ref mut frame,
+                frames,
+                ..
+            } => {
+                *frame += 1;
+                if *frame == frames {
                     self.end_capture()?;
                 }
             }
@@ -301,14 +696,15 @@ impl CommandStream {
         Ok(())
     }
 
-    pub fn start_capture(&mut self) -> anyhow::Result<()> {
+    pub fn start_capture(&mut self, frames: u32) -> anyhow::Result<()> {
         self.state = CommandStreamState::Capturing {
             start_instant: Instant::now(),
             shader_stream: vec![],
             processed_shader_stream: vec![],
             message_stream: vec![],
             d3d_stream: vec![],
-            frames: 0,
+            frame: 0,
+            frames,
         };
 
         Ok(())
@@ -320,6 +716,7 @@ impl CommandStream {
             processed_shader_stream,
             d3d_stream,
             message_stream,
+            frames,
             ..
         } = &self.state
         {
@@ -327,10 +724,7 @@ impl CommandStream {
             for cmd in shader_stream {
                 shader_streams
                     .entry(cmd.thread_id)
-                    .or_insert(Stream {
-                        stream: vec![],
-                        selected_index: None,
-                    })
+                    .or_insert_with(|| Stream::from(vec![]))
                     .stream
                     .push(cmd.clone());
             }
@@ -341,6 +735,8 @@ impl CommandStream {
                 processed_shader_stream: processed_shader_stream.clone().into(),
                 d3d_stream: d3d_stream.clone().into(),
                 message_stream: message_stream.clone().into(),
+                frames: *frames,
+                loaded_from: None,
             };
         }
         Ok(())
@@ -354,16 +750,18 @@ impl CommandStream {
         stream: &mut Vec<Command<PayloadType>>,
         address: Option<*const kernel::ShaderCommand>,
         start_instant: &Instant,
+        frame: u32,
         payload: PayloadType,
     ) -> anyhow::Result<()> {
         use windows::Win32::System::Threading::GetCurrentThreadId;
-        let backtrace = backtrace::Backtrace::new_unresolved();
+        let callstack = Callstack::Live(backtrace::Backtrace::new_unresolved());
 
         stream.push(Command::<PayloadType> {
             payload,
             address,
-            backtrace,
+            callstack,
             thread_id: unsafe { GetCurrentThreadId() },
+            frame,
             duration: Instant::now() - *start_instant,
         });
 
@@ -379,8 +777,15 @@ impl CommandStream {
             CommandStreamState::Capturing {
                 shader_stream,
                 start_instant,
+                frame,
                 ..
-            } => Self::push_back_command_to_stream(shader_stream, address, start_instant, payload),
+            } => Self::push_back_command_to_stream(
+                shader_stream,
+                address,
+                start_instant,
+                *frame,
+                payload,
+            ),
             _ => Ok(()),
         }
     }
@@ -390,7 +795,7 @@ impl CommandStream {
         match cmd_type {
             ShaderCommandType::SetRenderTargets => unsafe {
                 let rts = cmd.payload().set_render_targets.get_render_target_slice();
-                ShaderPayload::SetRenderTargets(rts.iter().map(|x| Ptr(*x)).collect())
+                ShaderPayload::SetRenderTargets(rts.iter().map(|x| Ptr(*x as *const _)).collect())
             },
             ShaderCommandType::SetViewports => ShaderPayload::SetViewports,
             ShaderCommandType::SetViewportsFancy => ShaderPayload::SetViewportsFancy,
@@ -404,15 +809,15 @@ impl CommandStream {
             ShaderCommandType::CopyTexture => unsafe {
                 let p = &cmd.payload().copy_texture;
                 ShaderPayload::CopyTexture {
-                    dst: Ptr(*p.dst_resource_ptr()),
-                    src: Ptr(*p.src_resource_ptr()),
+                    dst: Ptr(*p.dst_resource_ptr() as *const _),
+                    src: Ptr(*p.src_resource_ptr() as *const _),
                 }
             },
             ShaderCommandType::UnknownDraw => unsafe {
                 let p = &cmd.payload().unknown_draw;
                 ShaderPayload::UnknownDraw {
-                    render_target: Ptr(*p.render_target_ptr()),
-                    sampled_texture: Ptr(*p.sampled_texture_ptr()),
+                    render_target: Ptr(*p.render_target_ptr() as *const _),
+                    sampled_texture: Ptr(*p.sampled_texture_ptr() as *const _),
                 }
             },
             ShaderCommandType::CopyResource => ShaderPayload::CopyResource,
@@ -442,11 +847,13 @@ impl CommandStream {
             CommandStreamState::Capturing {
                 processed_shader_stream,
                 start_instant,
+                frame,
                 ..
             } => Self::push_back_command_to_stream(
                 processed_shader_stream,
                 Some(cmd as *const kernel::ShaderCommand),
                 start_instant,
+                *frame,
                 Self::shader_command_to_payload(cmd),
             ),
             _ => Ok(()),
@@ -458,23 +865,27 @@ impl CommandStream {
             CommandStreamState::Capturing {
                 d3d_stream,
                 start_instant,
+                frame,
                 ..
-            } => Self::push_back_command_to_stream(d3d_stream, None, start_instant, payload),
+            } => {
+                Self::push_back_command_to_stream(d3d_stream, None, start_instant, *frame, payload)
+            }
             _ => Ok(()),
         }
     }
 
-    #[allow(dead_code)]
     pub fn add_message(&mut self, msg: String, submsgs: Vec<String>) -> anyhow::Result<()> {
         match &mut self.state {
             CommandStreamState::Capturing {
                 message_stream,
                 start_instant,
+                frame,
                 ..
             } => Self::push_back_command_to_stream(
                 message_stream,
                 None,
                 start_instant,
+                *frame,
                 (msg, submsgs),
             ),
             _ => Ok(()),
@@ -484,4 +895,12 @@ impl CommandStream {
     pub fn draw_ui(&mut self) -> anyhow::Result<()> {
         self.ui.draw(&mut self.state)
     }
+
+    /// Draws a command from outside of a stream, like the creation of a resource.
+    pub fn draw_command<PayloadType: Payload>(
+        &mut self,
+        cmd: &Command<PayloadType>,
+    ) -> anyhow::Result<()> {
+        self.ui.draw_cmd(None, cmd)
+    }
 }
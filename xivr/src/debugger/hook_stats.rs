@@ -0,0 +1,185 @@
+use crate::hooks::{CallStatsSnapshot, HookRegistry};
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use cimgui as ig;
+
+const FRAMES_OF_HISTORY: usize = 240;
+
+/// What a hook did in each of the last `FRAMES_OF_HISTORY` frames.
+#[derive(Default)]
+struct HookHistory {
+    last: CallStatsSnapshot,
+    calls: VecDeque<f32>,
+    own_ms: VecDeque<f32>,
+}
+
+impl HookHistory {
+    fn push(&mut self, snapshot: CallStatsSnapshot) {
+        let frame = snapshot.since(&self.last);
+        self.last = snapshot;
+
+        for (history, value) in [
+            (&mut self.calls, frame.calls as f32),
+            (&mut self.own_ms, as_ms(frame.own())),
+        ] {
+            if history.len() == FRAMES_OF_HISTORY {
+                history.pop_front();
+            }
+            history.push_back(value);
+        }
+    }
+}
+
+fn as_ms(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
+
+fn as_us(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1_000_000.0
+}
+
+/// Keeps per-frame histories of each hook's `CallStats`, and shows them alongside the
+/// totals.
+pub struct HookStats {
+    histories: HashMap<&'static str, HookHistory>,
+}
+
+impl HookStats {
+    pub fn new() -> HookStats {
+        HookStats {
+            histories: HashMap::new(),
+        }
+    }
+
+    pub fn pre_update(&mut self) {
+        if let Some(registry) = HookRegistry::get() {
+            for (hook, _) in registry.hooks() {
+                self.histories
+                    .entry(hook.name)
+                    .or_default()
+                    .push(hook.stats.snapshot());
+            }
+        }
+    }
+
+    pub fn draw_ui(&mut self) -> anyhow::Result<()> {
+        let registry = match HookRegistry::get() {
+            Some(registry) => registry,
+            None => {
+                ig::text("Hooks have not been installed");
+                return Ok(());
+            }
+        };
+
+        if ig::button("Reset", None)? {
+            for (hook, _) in registry.hooks() {
+                hook.stats.reset();
+            }
+            self.histories.clear();
+        }
+
+        let mut rows: Vec<_> = registry
+            .hooks()
+            .map(|(hook, _)| (hook.name, hook.stats.snapshot()))
+            .collect();
+
+        const COLUMNS: [&str; 9] = [
+            "Name",
+            "Calls",
+            "Total (ms)",
+            "Own (ms)",
+            "Original (ms)",
+            "Min (us)",
+            "Max (us)",
+            "Calls/frame",
+            "Own ms/frame",
+        ];
+        if ig::begin_table(
+            "xivr_debug_tab_hook_stats",
+            COLUMNS.len() as i32,
+            Some(ig::TableFlags::Sortable),
+            None,
+            None,
+        )? {
+            for header in COLUMNS {
+                ig::table_setup_column(header, None, None, None)?;
+            }
+            ig::table_headers_row();
+
+            if let Some((column, direction)) = ig::table_get_sort_specs() {
+                let histories = &self.histories;
+                let latest = |name: &str, column: usize| {
+                    histories.get(name).and_then(|h| {
+                        let values = if column == 7 { &h.calls } else { &h.own_ms };
+                        values.back().copied()
+                    })
+                };
+                rows.sort_by(|(a_name, a), (b_name, b)| {
+                    let key = |name: &str, s: &CallStatsSnapshot| match column {
+                        1 => s.calls as f32,
+                        2 => as_ms(s.total),
+                        3 => as_ms(s.own()),
+                        4 => as_ms(s.original),
+                        5 => as_us(s.min),
+                        6 => as_us(s.max),
+                        7 | 8 => latest(name, column as usize).unwrap_or_default(),
+                        _ => 0.0,
+                    };
+                    let ordering = if column == 0 {
+                        a_name.cmp(b_name)
+                    } else {
+                        key(a_name, a).total_cmp(&key(b_name, b))
+                    };
+                    match direction {
+                        ig::SortDirection::Descending => ordering.reverse(),
+                        _ => ordering,
+                    }
+                });
+            }
+
+            for (name, stats) in rows {
+                ig::table_next_row(None, None);
+                ig::table_next_column();
+                ig::text(name);
+                ig::table_next_column();
+                ig::textf!("{}", stats.calls);
+                ig::table_next_column();
+                ig::textf!("{:.3}", as_ms(stats.total));
+                ig::table_next_column();
+                ig::textf!("{:.3}", as_ms(stats.own()));
+                ig::table_next_column();
+                ig::textf!("{:.3}", as_ms(stats.original));
+                ig::table_next_column();
+                ig::textf!("{:.1}", as_us(stats.min));
+                ig::table_next_column();
+                ig::textf!("{:.1}", as_us(stats.max));
+
+                let history = self.histories.get(name);
+                for (label, values, precision) in [
+                    ("calls", history.map(|h| &h.calls), 0),
+                    ("own_ms", history.map(|h| &h.own_ms), 3),
+                ] {
+                    ig::table_next_column();
+                    if let Some(values) = values {
+                        let values: Vec<f32> = values.iter().copied().collect();
+                        let latest = values.last().copied().unwrap_or_default();
+                        let overlay = format!("{:.*}", precision, latest);
+                        ig::plot_lines(
+                            &format!("##{}_{}", label, name),
+                            &values,
+                            Some(&overlay),
+                            Some(0.0),
+                            None,
+                            Some(ig::Vec2::new(150.0, 30.0)),
+                        )?;
+                    }
+                }
+            }
+            ig::end_table();
+        }
+
+        Ok(())
+    }
+}
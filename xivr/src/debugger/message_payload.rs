@@ -2,18 +2,14 @@ use crate::debugger::payload::{Command, Payload};
 
 use cimgui as ig;
 
-pub type MessagePayload = (String, Vec<String>);
+pub use xivr_capture::MessagePayload;
 
 impl Payload for MessagePayload {
-    fn title(&self) -> String {
-        self.0.clone()
-    }
-
     fn colour(&self) -> ig::Color {
         ig::Color::from_hsv((self.1.len() as f32 / 128.0).min(1.0), 0.7, 0.8)
     }
 
-    fn draw(&self) -> anyhow::Result<()> {
+    fn draw(&self, _read_only: bool) -> anyhow::Result<()> {
         for t in &self.1 {
             ig::bulletf!("{}", t);
         }
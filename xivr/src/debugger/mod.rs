@@ -5,16 +5,28 @@ pub mod shader_payload;
 
 mod util;
 
+mod capture;
+
 mod command_stream;
 pub use command_stream::*;
 
+mod hook_stats;
+pub use hook_stats::*;
+
+use crate::debugger::d3d_payload::{D3DCommand, D3DPayload};
+use crate::debugger::payload::{Callstack, Command, Describe};
 use crate::debugger::util::dxgi_format_to_str;
 use crate::game::graphics::kernel::{Device, Texture};
+use crate::signatures;
 use crate::singleton;
 
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
+use std::os::raw::c_void;
 use std::sync::Mutex;
+use std::time::Instant;
 
 use windows::Win32::Graphics::Direct3D11 as d3d;
 
@@ -35,11 +47,49 @@ impl Hash for InspectedResource {
     }
 }
 
+/// How resources were created, by the address of the resource. Entries are replaced when
+/// an address is reused, and the oldest are forgotten once there are too many, as
+/// releasing a resource isn't seen.
+#[derive(Default)]
+struct ResourceCreations {
+    creations: HashMap<usize, D3DCommand>,
+    order: VecDeque<usize>,
+}
+
+impl ResourceCreations {
+    const CAPACITY: usize = 1024;
+
+    fn insert(&mut self, resource: usize, command: D3DCommand) {
+        if self.creations.insert(resource, command).is_none() {
+            self.order.push_back(resource);
+        }
+        while self.order.len() > Self::CAPACITY {
+            if let Some(resource) = self.order.pop_front() {
+                self.creations.remove(&resource);
+            }
+        }
+    }
+}
+
+/// The parts of the debugger's state that survive a reload.
+#[derive(Serialize, Deserialize)]
+pub struct DebuggerState {
+    inspected_textures: Vec<usize>,
+    rtti_address: String,
+}
+
 pub struct Debugger {
     pub command_stream: Mutex<CommandStream>,
+    hook_stats: Mutex<HookStats>,
+    start_instant: Instant,
+    /// How the render targets created while the device hook was enabled were created.
+    resource_creations: Mutex<ResourceCreations>,
+    selected_creation: Option<D3DCommand>,
     inspected_textures: HashSet<&'static Texture>,
     inspected_resources: HashSet<InspectedResource>,
     some_global_struct: *const u8,
+    some_global_struct_offset: usize,
+    rtti_address: String,
 }
 singleton!(Debugger);
 
@@ -50,19 +100,83 @@ impl Debugger {
         let inspected_resources = HashSet::new();
 
         let module = crate::util::game_module_mut()?;
-        let mystery_function: fn() -> *const u8 = unsafe {
-            std::mem::transmute(module.scan_for_relative_callsite("E8 ? ? ? ? 48 8B 58 60")?)
-        };
+        let mystery_function: fn() -> *const u8 =
+            unsafe { std::mem::transmute(module.resolve(signatures::GET_SOME_GLOBAL_STRUCT)?) };
         let some_global_struct = mystery_function();
+        let some_global_struct_offset = module
+            .resolve_captures(signatures::GET_SOME_GLOBAL_STRUCT)?
+            .get("offset")?;
 
         Ok(Debugger {
             command_stream,
+            hook_stats: Mutex::new(HookStats::new()),
+            start_instant: Instant::now(),
+            resource_creations: Mutex::new(ResourceCreations::default()),
+            selected_creation: None,
             inspected_textures,
             inspected_resources,
             some_global_struct,
+            some_global_struct_offset,
+            rtti_address: String::new(),
         })
     }
 
+    pub fn record_resource_creation(&self, resource: *mut c_void, payload: D3DPayload) {
+        use windows::Win32::System::Threading::GetCurrentThreadId;
+
+        let command = Command {
+            payload,
+            address: None,
+            callstack: Callstack::Live(backtrace::Backtrace::new_unresolved()),
+            thread_id: unsafe { GetCurrentThreadId() },
+            frame: 0,
+            duration: Instant::now() - self.start_instant,
+        };
+        let mut resource_creations = self.resource_creations.lock().unwrap();
+        resource_creations.insert(resource as usize, command);
+    }
+
+    fn texture_creation(&self, texture: &Texture) -> Option<D3DCommand> {
+        let resource: usize = unsafe { std::mem::transmute_copy(texture.texture()) };
+        let resource_creations = self.resource_creations.lock().unwrap();
+        resource_creations.creations.get(&resource).cloned()
+    }
+
+    pub fn save_state(&self) -> DebuggerState {
+        DebuggerState {
+            inspected_textures: self
+                .inspected_textures
+                .iter()
+                .map(|t| *t as *const Texture as usize)
+                .collect(),
+            rtti_address: self.rtti_address.clone(),
+        }
+    }
+
+    /// Inspected textures are only restored if they're still render targets, as anything
+    /// else may have been freed since they were saved.
+    pub fn restore_state(&mut self, state: DebuggerState) {
+        use crate::game::graphics::render::RenderTargetManager;
+
+        let render_targets: HashSet<usize> = unsafe {
+            let swapchain = &*Device::get().swapchain_ptr();
+            RenderTargetManager::get()
+                .get_render_targets()
+                .into_iter()
+                .map(|(_, texture)| texture as usize)
+                .chain(std::iter::once(
+                    *swapchain.back_buffer() as *const Texture as usize
+                ))
+                .collect()
+        };
+        for address in state.inspected_textures {
+            if render_targets.contains(&address) {
+                self.inspect_texture(unsafe { &*(address as *const Texture) });
+            }
+        }
+        self.rtti_address = state.rtti_address;
+    }
+
     pub fn inspect_texture(&mut self, texture: &'static Texture) {
         self.inspected_textures.insert(texture);
     }
@@ -238,6 +352,18 @@ impl Debugger {
             ig::table_next_column();
             ig::textf!("{}", dxgi_format_to_str(desc.Format));
         }
+        {
+            ig::table_next_column();
+            match self.texture_creation(texture) {
+                Some(creation) => {
+                    let label = format!("{}##{:X?}", creation.payload.title(), texture as *const _);
+                    if ig::small_button(&label)? {
+                        self.selected_creation = Some(creation);
+                    }
+                }
+                None => ig::text("Unknown"),
+            }
+        }
 
         Ok(())
     }
@@ -257,12 +383,15 @@ impl Debugger {
         if ig::begin_child("xivr_debug_tab_rts_child", None, None, None)? {
             if ig::collapsing_header("Mystery structure", None, None)? {
                 let texture: &Texture = unsafe {
-                    let some_struct = *(self.some_global_struct.add(0x60) as *const *const u8);
+                    let some_struct = *(self.some_global_struct.add(self.some_global_struct_offset)
+                        as *const *const u8);
                     &**(some_struct.add(0x10) as *const *const Texture)
                 };
 
-                if ig::begin_table("xivr_debug_tab_rts_mystery", 6, None, None, None)? {
-                    setup_columns(["Preview", "Address", "Title", "Width", "Height", "Format"])?;
+                if ig::begin_table("xivr_debug_tab_rts_mystery", 7, None, None, None)? {
+                    setup_columns([
+                        "Preview", "Address", "Title", "Width", "Height", "Format", "Creation",
+                    ])?;
 
                     self.draw_render_target("Backbuffer (real?)", texture)?;
 
@@ -272,8 +401,10 @@ impl Debugger {
 
             if ig::collapsing_header("Swapchain", None, None)? {
                 let swapchain = unsafe { &*kernel::Device::get().swapchain_ptr() };
-                if ig::begin_table("xivr_debug_tab_rts_swapchain", 6, None, None, None)? {
-                    setup_columns(["Preview", "Address", "Title", "Width", "Height", "Format"])?;
+                if ig::begin_table("xivr_debug_tab_rts_swapchain", 7, None, None, None)? {
+                    setup_columns([
+                        "Preview", "Address", "Title", "Width", "Height", "Format", "Creation",
+                    ])?;
 
                     self.draw_render_target("Backbuffer", unsafe {
                         &*(*swapchain.back_buffer() as *const _)
@@ -285,8 +416,10 @@ impl Debugger {
 
             if ig::collapsing_header("Render Target Manager", None, None)? {
                 let textures = unsafe { render::RenderTargetManager::get().get_render_targets() };
-                if ig::begin_table("xivr_debug_tab_rts_rtm", 6, None, None, None)? {
-                    setup_columns(["Preview", "Address", "Offset", "Width", "Height", "Format"])?;
+                if ig::begin_table("xivr_debug_tab_rts_rtm", 7, None, None, None)? {
+                    setup_columns([
+                        "Preview", "Address", "Offset", "Width", "Height", "Format", "Creation",
+                    ])?;
 
                     for (offset, texture) in textures.into_iter() {
                         self.draw_render_target(&format!("0x{:X}", offset), unsafe { &*texture })?;
@@ -295,13 +428,117 @@ impl Debugger {
                     ig::end_table();
                 }
             }
+
+            if let Some(creation) = &self.selected_creation {
+                ig::separator();
+                let mut command_stream = self.command_stream.lock().unwrap();
+                command_stream.draw_command(creation)?;
+            }
             ig::end_child();
         }
 
         Ok(())
     }
 
+    pub fn draw_rtti(&mut self) -> anyhow::Result<()> {
+        use cimgui as ig;
+
+        ig::input_text(
+            "Object address",
+            &mut self.rtti_address,
+            32,
+            Some(ig::InputTextFlags::CharsHexadecimal),
+        )?;
+
+        let address = self.rtti_address.trim().trim_start_matches("0x");
+        if address.is_empty() {
+            return Ok(());
+        }
+        let object = match usize::from_str_radix(address, 16) {
+            Ok(object) => object as *const *const u8,
+            Err(_) => {
+                ig::text("Invalid address");
+                return Ok(());
+            }
+        };
+
+        let vtable = match crate::util::read_memory(object) {
+            Some(vtable) => vtable,
+            None => {
+                ig::text("Unable to read object");
+                return Ok(());
+            }
+        };
+        ig::bulletf!("Vtable: {:X?}", vtable);
+
+        let module = crate::util::game_module_mut()?;
+        match module.vtable_class(vtable) {
+            Some(class) => {
+                ig::bulletf!("Vtable RVA: 0x{:X}", class.rva);
+                ig::bulletf!("Class: {}", class.class_name);
+                ig::bulletf!("Offset in complete object: 0x{:X}", class.offset);
+            }
+            None => ig::text("Not a vtable with RTTI in the game module"),
+        }
+
+        Ok(())
+    }
+
+    pub fn draw_hooks(&mut self) -> anyhow::Result<()> {
+        use crate::hooks::{HookRegistry, HookStatus};
+        use cimgui as ig;
+
+        let registry = match HookRegistry::get_mut() {
+            Some(registry) => registry,
+            None => {
+                ig::text("Hooks have not been installed");
+                return Ok(());
+            }
+        };
+
+        // Toggled after drawing, so that we're not changing the registry while reading it.
+        let mut toggle = None;
+        if ig::begin_table("xivr_debug_tab_hooks", 4, None, None, None)? {
+            for header in ["Name", "Source", "Status", ""] {
+                ig::table_setup_column(header, None, None, None)?;
+            }
+            ig::table_headers_row();
+
+            for (hook, status) in registry.hooks() {
+                ig::table_next_row(None, None);
+                ig::table_next_column();
+                ig::text(hook.name);
+                ig::table_next_column();
+                ig::textf!("{}", hook.source);
+                ig::table_next_column();
+                ig::textf!("{}", status);
+                ig::table_next_column();
+                let enabled = *status == HookStatus::Installed;
+                let label = if enabled { "Disable" } else { "Enable" };
+                if ig::small_button(&format!("{}##{}", label, hook.name))? {
+                    toggle = Some((hook.name, enabled));
+                }
+            }
+            ig::end_table();
+        }
+
+        if let Some((name, enabled)) = toggle {
+            let res = if enabled {
+                registry.disable(name)
+            } else {
+                registry.enable(name)
+            };
+            if let Err(e) = res {
+                crate::log!("error", "error while toggling {} hook: {}", name, e);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn pre_update(&mut self) -> anyhow::Result<()> {
+        self.hook_stats.lock().unwrap().pre_update();
+
         let mut command_stream = self.command_stream.lock().unwrap();
         command_stream.pre_update()
     }
@@ -321,6 +558,18 @@ impl Debugger {
                     self.draw_render_targets()?;
                     ig::end_tab_item();
                 }
+                if ig::begin_tab_item("RTTI", None, None)? {
+                    self.draw_rtti()?;
+                    ig::end_tab_item();
+                }
+                if ig::begin_tab_item("Hooks", None, None)? {
+                    self.draw_hooks()?;
+                    ig::end_tab_item();
+                }
+                if ig::begin_tab_item("Hook Stats", None, None)? {
+                    self.hook_stats.lock().unwrap().draw_ui()?;
+                    ig::end_tab_item();
+                }
                 if let Some(xr) = XR::get_mut() {
                     if ig::begin_tab_item("Framebuffers", None, None)? {
                         xr.draw_ui_framebuffers()?;
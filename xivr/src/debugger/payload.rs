@@ -1,18 +1,99 @@
 use crate::game::graphics::kernel;
+use crate::util::symbols::Symbolizer;
 
 use std::time::Duration;
 
-pub trait Payload {
-    fn title(&self) -> String;
+pub use xivr_capture::{Describe, Frame, Recorded, SavedCommand};
+
+pub trait Payload: Describe {
     fn colour(&self) -> cimgui::Color;
-    fn draw(&self) -> anyhow::Result<()>;
+    /// Draws the payload's data. Read-only payloads were loaded from a saved capture, so
+    /// the pointers they hold are from another session and must not be followed.
+    fn draw(&self, read_only: bool) -> anyhow::Result<()>;
+}
+
+/// The frames that led to a command. Live callstacks are only resolved when they're
+/// saved, as resolving every command's callstack during a capture would be far too slow.
+#[derive(Clone)]
+pub enum Callstack {
+    Live(backtrace::Backtrace),
+    Saved(Vec<Frame>),
+}
+
+impl Callstack {
+    /// The frames of the hooks and the debugger that recorded the command.
+    pub const RECORDING_FRAMES: usize = 5;
+
+    /// Resolves the callstack with `symbolizer`, which should be shared between all of the
+    /// callstacks being resolved, as they have most of their frames in common.
+    pub fn resolve(&self, symbolizer: &mut Symbolizer) -> Vec<Frame> {
+        match self {
+            Callstack::Live(backtrace) => {
+                symbolizer.resolve(backtrace.frames().iter().skip(Self::RECORDING_FRAMES))
+            }
+            Callstack::Saved(frames) => frames.clone(),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct Command<PayloadType> {
     pub payload: PayloadType,
     pub address: Option<*const kernel::ShaderCommand>,
-    pub backtrace: backtrace::Backtrace,
+    pub callstack: Callstack,
     pub thread_id: u32,
+    /// The frame of the capture the command was recorded in.
+    pub frame: u32,
     pub duration: Duration,
 }
+
+impl<PayloadType: Clone> Command<PayloadType> {
+    pub fn to_saved<SavedPayloadType: From<PayloadType>>(
+        &self,
+        symbolizer: &mut Symbolizer,
+    ) -> SavedCommand<SavedPayloadType> {
+        SavedCommand {
+            payload: self.payload.clone().into(),
+            address: self.address.map(|a| a as usize),
+            callstack: self.callstack.resolve(symbolizer),
+            thread_id: self.thread_id,
+            frame: self.frame,
+            duration: self.duration,
+        }
+    }
+}
+
+impl<PayloadType, SavedPayloadType: Into<PayloadType>> From<SavedCommand<SavedPayloadType>>
+    for Command<PayloadType>
+{
+    fn from(saved: SavedCommand<SavedPayloadType>) -> Self {
+        Command {
+            payload: saved.payload.into(),
+            address: saved.address.map(|a| a as *const _),
+            callstack: Callstack::Saved(saved.callstack),
+            thread_id: saved.thread_id,
+            frame: saved.frame,
+            duration: saved.duration,
+        }
+    }
+}
+
+impl<PayloadType: Describe> Recorded for Command<PayloadType> {
+    type Payload = PayloadType;
+
+    fn payload(&self) -> &PayloadType {
+        &self.payload
+    }
+
+    fn address(&self) -> Option<usize> {
+        self.address.map(|a| a as usize)
+    }
+
+    fn thread_id(&self) -> u32 {
+        self.thread_id
+    }
+
+    fn frame(&self) -> u32 {
+        self.frame
+    }
+}
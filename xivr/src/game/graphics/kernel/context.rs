@@ -1,7 +1,8 @@
 #![allow(dead_code)]
 
-use crate::game::graphics::kernel::{ImmediateContext, ShaderCommand};
-use crate::hooks::graphics::kernel::immediate_context::{XIVRCommand, XIVRCommandPayload};
+use crate::game::graphics::kernel::ShaderCommand;
+use crate::hooks::graphics::kernel::immediate_context::{self, XIVRCommand, XIVRCommandData};
+use crate::signatures;
 use macros::game_class;
 
 use std::arch::asm;
@@ -12,8 +13,8 @@ game_class!(Context, {
         [0x8] sort_key_maybe: u32,
     },
     functions: {
-        [signature("4C 8D 4A 0F 4C 8B C1")] fn alloc(&mut self, size: usize) -> *mut u8;
-        [signature("83 41 30 FF")] fn push_back_command(&mut self, cmd: &ShaderCommand) -> usize;
+        [signature(signatures::CONTEXT_ALLOC)] fn alloc(&mut self, size: usize) -> *mut u8;
+        [signature(signatures::CONTEXT_PUSH_BACK_COMMAND)] fn push_back_command(&mut self, cmd: &ShaderCommand) -> usize;
     }
 });
 
@@ -32,18 +33,25 @@ pub(self) unsafe extern "C" fn get_context(_tls_index: u32) -> &'static mut Cont
 impl Context {
     pub fn get_for_current_thread() -> anyhow::Result<&'static mut Context> {
         let module = crate::util::game_module_mut()?;
-        Ok(unsafe { get_context(module.tls_index()) })
+        Ok(unsafe { get_context(module.tls_index()?) })
     }
 
-    pub fn push_back_xivr_command(
-        &mut self,
-        callback: fn(&'static ImmediateContext, &XIVRCommandPayload) -> (),
-        payload: XIVRCommandPayload,
-    ) {
+    /// Queues `command` to be executed on the render thread when the game reaches this
+    /// point in its command stream, once for each pass over it (see `XIVRCommand`).
+    pub fn push_back_xivr_command<C: XIVRCommand>(&mut self, command: C) -> anyhow::Result<()> {
+        if !immediate_context::xivr_commands_enabled() {
+            anyhow::bail!("XIVR commands are not enabled");
+        }
+
         unsafe {
-            let cmd = self.alloc(std::mem::size_of::<XIVRCommand>()) as *mut XIVRCommand;
-            *cmd = XIVRCommand::new(callback, payload);
+            // The allocator's alignment isn't known, so make room to align the command.
+            let align = std::mem::align_of::<XIVRCommandData<C>>();
+            let size = std::mem::size_of::<XIVRCommandData<C>>() + align - 1;
+            let allocation = self.alloc(size);
+            let cmd = allocation.add(allocation.align_offset(align)) as *mut XIVRCommandData<C>;
+            cmd.write(XIVRCommandData::new(command));
             self.push_back_command(&*(cmd as *mut ShaderCommand));
         }
+        Ok(())
     }
 }
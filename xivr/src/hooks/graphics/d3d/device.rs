@@ -0,0 +1,219 @@
+#![allow(dead_code)]
+#![allow(non_snake_case)]
+
+use crate::debugger::d3d_payload::D3DPayload;
+use crate::debugger::Debugger;
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+
+use super::created_object;
+
+use windows::runtime::*;
+use windows::Win32::Foundation::{HANDLE, PSTR, PWSTR};
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11_BIND_DEPTH_STENCIL, D3D11_BIND_RENDER_TARGET, D3D11_BLEND_DESC, D3D11_BLEND_DESC1,
+    D3D11_BOX, D3D11_BUFFER_DESC, D3D11_COUNTER_DESC, D3D11_COUNTER_INFO, D3D11_COUNTER_TYPE,
+    D3D11_DEPTH_STENCIL_DESC, D3D11_DEPTH_STENCIL_VIEW_DESC, D3D11_FEATURE, D3D11_FENCE_FLAG,
+    D3D11_INPUT_ELEMENT_DESC, D3D11_PACKED_MIP_DESC, D3D11_QUERY_DESC, D3D11_QUERY_DESC1,
+    D3D11_RASTERIZER_DESC, D3D11_RASTERIZER_DESC1, D3D11_RASTERIZER_DESC2,
+    D3D11_RENDER_TARGET_VIEW_DESC, D3D11_RENDER_TARGET_VIEW_DESC1, D3D11_SAMPLER_DESC,
+    D3D11_SHADER_RESOURCE_VIEW_DESC, D3D11_SHADER_RESOURCE_VIEW_DESC1, D3D11_SO_DECLARATION_ENTRY,
+    D3D11_SUBRESOURCE_DATA, D3D11_SUBRESOURCE_TILING, D3D11_TEXTURE1D_DESC, D3D11_TEXTURE2D_DESC,
+    D3D11_TEXTURE2D_DESC1, D3D11_TEXTURE3D_DESC, D3D11_TEXTURE3D_DESC1, D3D11_TILE_SHAPE,
+    D3D11_UNORDERED_ACCESS_VIEW_DESC, D3D11_UNORDERED_ACCESS_VIEW_DESC1, D3D_FEATURE_LEVEL,
+};
+use windows::Win32::Graphics::Dxgi::DXGI_FORMAT;
+
+use std::os::raw::c_void;
+
+// Resource, view and shader creation record what was created, and how, as the pointers
+// they're given don't outlive the call.
+macros::com_interface!(ID3D11Device, {
+    payload: DevicePayload,
+    callback: push_back_payload,
+    stats: STATS,
+    methods: {
+        fn QueryInterface(riid: *const GUID, ppvObject: *mut *mut c_void) -> HRESULT;
+        fn AddRef() -> u32;
+        fn Release() -> u32;
+        #[capture(desc: Option<D3D11_BUFFER_DESC> = pDesc.as_ref().copied())]
+        #[capture(buffer: *mut c_void = created_object(ppBuffer))]
+        fn CreateBuffer(pDesc: *const D3D11_BUFFER_DESC, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppBuffer: *mut *mut c_void) -> HRESULT;
+        #[capture(desc: Option<D3D11_TEXTURE1D_DESC> = pDesc.as_ref().copied())]
+        #[capture(texture: *mut c_void = created_object(ppTexture1D))]
+        fn CreateTexture1D(pDesc: *const D3D11_TEXTURE1D_DESC, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppTexture1D: *mut *mut c_void) -> HRESULT;
+        #[capture(desc: Option<D3D11_TEXTURE2D_DESC> = pDesc.as_ref().copied())]
+        #[capture(texture: *mut c_void = created_object(ppTexture2D))]
+        fn CreateTexture2D(pDesc: *const D3D11_TEXTURE2D_DESC, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppTexture2D: *mut *mut c_void) -> HRESULT;
+        #[capture(desc: Option<D3D11_TEXTURE3D_DESC> = pDesc.as_ref().copied())]
+        #[capture(texture: *mut c_void = created_object(ppTexture3D))]
+        fn CreateTexture3D(pDesc: *const D3D11_TEXTURE3D_DESC, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppTexture3D: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppSRView))]
+        fn CreateShaderResourceView(pResource: *mut c_void, pDesc: *const D3D11_SHADER_RESOURCE_VIEW_DESC, ppSRView: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppUAView))]
+        fn CreateUnorderedAccessView(pResource: *mut c_void, pDesc: *const D3D11_UNORDERED_ACCESS_VIEW_DESC, ppUAView: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppRTView))]
+        fn CreateRenderTargetView(pResource: *mut c_void, pDesc: *const D3D11_RENDER_TARGET_VIEW_DESC, ppRTView: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppDepthStencilView))]
+        fn CreateDepthStencilView(pResource: *mut c_void, pDesc: *const D3D11_DEPTH_STENCIL_VIEW_DESC, ppDepthStencilView: *mut *mut c_void) -> HRESULT;
+        fn CreateInputLayout(pInputElementDescs: *const D3D11_INPUT_ELEMENT_DESC, NumElements: u32, pShaderBytecodeWithInputSignature: *const c_void, BytecodeLength: usize, ppInputLayout: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppVertexShader))]
+        fn CreateVertexShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppVertexShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppGeometryShader))]
+        fn CreateGeometryShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppGeometryShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppGeometryShader))]
+        fn CreateGeometryShaderWithStreamOutput(pShaderBytecode: *const c_void, BytecodeLength: usize, pSODeclaration: *const D3D11_SO_DECLARATION_ENTRY, NumEntries: u32, pBufferStrides: *const u32, NumStrides: u32, RasterizedStream: u32, pClassLinkage: *mut c_void, ppGeometryShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppPixelShader))]
+        fn CreatePixelShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppPixelShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppHullShader))]
+        fn CreateHullShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppHullShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppDomainShader))]
+        fn CreateDomainShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppDomainShader: *mut *mut c_void) -> HRESULT;
+        #[capture(shader: *mut c_void = created_object(ppComputeShader))]
+        fn CreateComputeShader(pShaderBytecode: *const c_void, BytecodeLength: usize, pClassLinkage: *mut c_void, ppComputeShader: *mut *mut c_void) -> HRESULT;
+        fn CreateClassLinkage(ppLinkage: *mut *mut c_void) -> HRESULT;
+        fn CreateBlendState(pBlendStateDesc: *const D3D11_BLEND_DESC, ppBlendState: *mut *mut c_void) -> HRESULT;
+        fn CreateDepthStencilState(pDepthStencilDesc: *const D3D11_DEPTH_STENCIL_DESC, ppDepthStencilState: *mut *mut c_void) -> HRESULT;
+        fn CreateRasterizerState(pRasterizerDesc: *const D3D11_RASTERIZER_DESC, ppRasterizerState: *mut *mut c_void) -> HRESULT;
+        fn CreateSamplerState(pSamplerDesc: *const D3D11_SAMPLER_DESC, ppSamplerState: *mut *mut c_void) -> HRESULT;
+        fn CreateQuery(pQueryDesc: *const D3D11_QUERY_DESC, ppQuery: *mut *mut c_void) -> HRESULT;
+        fn CreatePredicate(pPredicateDesc: *const D3D11_QUERY_DESC, ppPredicate: *mut *mut c_void) -> HRESULT;
+        fn CreateCounter(pCounterDesc: *const D3D11_COUNTER_DESC, ppCounter: *mut *mut c_void) -> HRESULT;
+        fn CreateDeferredContext(ContextFlags: u32, ppDeferredContext: *mut *mut c_void) -> HRESULT;
+        fn OpenSharedResource(hResource: HANDLE, ReturnedInterface: *const GUID, ppResource: *mut *mut c_void) -> HRESULT;
+        fn CheckFormatSupport(Format: DXGI_FORMAT, pFormatSupport: *mut u32) -> HRESULT;
+        fn CheckMultisampleQualityLevels(Format: DXGI_FORMAT, SampleCount: u32, pNumQualityLevels: *mut u32) -> HRESULT;
+        fn CheckCounterInfo(pCounterInfo: *mut D3D11_COUNTER_INFO);
+        fn CheckCounter(pDesc: *const D3D11_COUNTER_DESC, pType: *mut D3D11_COUNTER_TYPE, pActiveCounters: *mut u32, szName: PSTR, pNameLength: *mut u32, szUnits: PSTR, pUnitsLength: *mut u32, szDescription: PSTR, pDescriptionLength: *mut u32) -> HRESULT;
+        fn CheckFeatureSupport(Feature: D3D11_FEATURE, pFeatureSupportData: *mut c_void, FeatureSupportDataSize: u32) -> HRESULT;
+        fn GetPrivateData(guid: *const GUID, pDataSize: *mut u32, pData: *mut c_void) -> HRESULT;
+        fn SetPrivateData(guid: *const GUID, DataSize: u32, pData: *const c_void) -> HRESULT;
+        fn SetPrivateDataInterface(guid: *const GUID, pData: *mut IUnknown) -> HRESULT;
+        fn GetFeatureLevel() -> D3D_FEATURE_LEVEL;
+        fn GetCreationFlags() -> u32;
+        fn GetDeviceRemovedReason() -> HRESULT;
+        fn GetImmediateContext(ppImmediateContext: *mut *mut c_void);
+        fn SetExceptionMode(RaiseFlags: u32) -> HRESULT;
+        fn GetExceptionMode() -> u32;
+        fn GetImmediateContext1(ppImmediateContext: *mut *mut c_void);
+        fn CreateDeferredContext1(ContextFlags: u32, ppDeferredContext: *mut *mut c_void) -> HRESULT;
+        fn CreateBlendState1(pBlendStateDesc: *const D3D11_BLEND_DESC1, ppBlendState: *mut *mut c_void) -> HRESULT;
+        fn CreateRasterizerState1(pRasterizerDesc: *const D3D11_RASTERIZER_DESC1, ppRasterizerState: *mut *mut c_void) -> HRESULT;
+        fn CreateDeviceContextState(Flags: u32, pFeatureLevels: *const D3D_FEATURE_LEVEL, FeatureLevels: u32, SDKVersion: u32, EmulatedInterface: *const GUID, pChosenFeatureLevel: *mut D3D_FEATURE_LEVEL, ppContextState: *mut *mut c_void) -> HRESULT;
+        fn OpenSharedResource1(hResource: HANDLE, returnedInterface: *const GUID, ppResource: *mut *mut c_void) -> HRESULT;
+        fn OpenSharedResourceByName(lpName: PWSTR, dwDesiredAccess: u32, returnedInterface: *const GUID, ppResource: *mut *mut c_void) -> HRESULT;
+        fn GetImmediateContext2(ppImmediateContext: *mut *mut c_void);
+        fn CreateDeferredContext2(ContextFlags: u32, ppDeferredContext: *mut *mut c_void) -> HRESULT;
+        fn GetResourceTiling(pTiledResource: *mut c_void, pNumTilesForEntireResource: *mut u32, pPackedMipDesc: *mut D3D11_PACKED_MIP_DESC, pStandardTileShapeForNonPackedMips: *mut D3D11_TILE_SHAPE, pNumSubresourceTilings: *mut u32, FirstSubresourceTilingToGet: u32, pSubresourceTilingsForNonPackedMips: *mut D3D11_SUBRESOURCE_TILING);
+        fn CheckMultisampleQualityLevels1(Format: DXGI_FORMAT, SampleCount: u32, Flags: u32, pNumQualityLevels: *mut u32) -> HRESULT;
+        #[capture(desc: Option<D3D11_TEXTURE2D_DESC1> = pDesc1.as_ref().copied())]
+        #[capture(texture: *mut c_void = created_object(ppTexture2D))]
+        fn CreateTexture2D1(pDesc1: *const D3D11_TEXTURE2D_DESC1, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppTexture2D: *mut *mut c_void) -> HRESULT;
+        #[capture(desc: Option<D3D11_TEXTURE3D_DESC1> = pDesc1.as_ref().copied())]
+        #[capture(texture: *mut c_void = created_object(ppTexture3D))]
+        fn CreateTexture3D1(pDesc1: *const D3D11_TEXTURE3D_DESC1, pInitialData: *const D3D11_SUBRESOURCE_DATA, ppTexture3D: *mut *mut c_void) -> HRESULT;
+        fn CreateRasterizerState2(pRasterizerDesc: *const D3D11_RASTERIZER_DESC2, ppRasterizerState: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc1.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppSRView1))]
+        fn CreateShaderResourceView1(pResource: *mut c_void, pDesc1: *const D3D11_SHADER_RESOURCE_VIEW_DESC1, ppSRView1: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc1.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppUAView1))]
+        fn CreateUnorderedAccessView1(pResource: *mut c_void, pDesc1: *const D3D11_UNORDERED_ACCESS_VIEW_DESC1, ppUAView1: *mut *mut c_void) -> HRESULT;
+        #[capture(format: Option<DXGI_FORMAT> = pDesc1.as_ref().map(|desc| desc.Format))]
+        #[capture(view: *mut c_void = created_object(ppRTView1))]
+        fn CreateRenderTargetView1(pResource: *mut c_void, pDesc1: *const D3D11_RENDER_TARGET_VIEW_DESC1, ppRTView1: *mut *mut c_void) -> HRESULT;
+        fn CreateQuery1(pQueryDesc1: *const D3D11_QUERY_DESC1, ppQuery1: *mut *mut c_void) -> HRESULT;
+        fn GetImmediateContext3(ppImmediateContext: *mut *mut c_void);
+        fn CreateDeferredContext3(ContextFlags: u32, ppDeferredContext: *mut *mut c_void) -> HRESULT;
+        fn WriteToSubresource(pDstResource: *mut c_void, DstSubresource: u32, pDstBox: *const D3D11_BOX, pSrcData: *const c_void, SrcRowPitch: u32, SrcDepthPitch: u32);
+        fn ReadFromSubresource(pDstData: *mut c_void, DstRowPitch: u32, DstDepthPitch: u32, pSrcResource: *mut c_void, SrcSubresource: u32, pSrcBox: *const D3D11_BOX);
+        fn RegisterDeviceRemovedEvent(hEvent: HANDLE, pdwCookie: *mut u32) -> HRESULT;
+        fn UnregisterDeviceRemoved(dwCookie: u32);
+        fn OpenSharedFence(hFence: HANDLE, ReturnedInterface: *const GUID, ppFence: *mut *mut c_void) -> HRESULT;
+        fn CreateFence(InitialValue: u64, Flags: D3D11_FENCE_FLAG, ReturnedInterface: *const GUID, ppFence: *mut *mut c_void) -> HRESULT;
+    },
+});
+
+impl DevicePayload {
+    /// The texture this call created, if any.
+    pub fn created_texture(&self) -> Option<*mut c_void> {
+        let texture = match self {
+            Self::CreateTexture1D(.., texture)
+            | Self::CreateTexture2D(.., texture)
+            | Self::CreateTexture3D(.., texture)
+            | Self::CreateTexture2D1(.., texture)
+            | Self::CreateTexture3D1(.., texture) => *texture,
+            _ => return None,
+        };
+        Some(texture).filter(|t| !t.is_null())
+    }
+
+    /// Whether this call created a 2D texture that can be rendered to. Only their
+    /// creations are shown in the debugger.
+    pub fn creates_render_target(&self) -> bool {
+        let bind_flags = match self {
+            Self::CreateTexture2D(.., Some(desc), _) => desc.BindFlags,
+            Self::CreateTexture2D1(.., Some(desc), _) => desc.BindFlags,
+            _ => return false,
+        };
+        bind_flags.0 & (D3D11_BIND_RENDER_TARGET.0 | D3D11_BIND_DEPTH_STENCIL.0) != 0
+    }
+}
+
+fn push_back_payload(payload: DevicePayload) {
+    if let Some(debugger) = Debugger::get_mut() {
+        // Other textures are streamed in and out for as long as the game runs, so
+        // recording all of them would grow without bound.
+        match payload.created_texture() {
+            Some(texture) if payload.creates_render_target() => {
+                debugger.record_resource_creation(texture, D3DPayload::Device(payload.clone()))
+            }
+            _ => {}
+        }
+
+        // The debugger creates resources while holding the command stream (to inspect
+        // them, for example), so calls made while it's locked are dropped instead of
+        // waiting on it.
+        if let Ok(mut command_stream) = debugger.command_stream.try_lock() {
+            command_stream
+                .add_d3d_command(D3DPayload::Device(payload))
+                .unwrap();
+        }
+    }
+}
+
+static STATS: CallStats = CallStats::new();
+
+const PATCH_GROUP: &str = "d3d_device_vtable";
+
+// Creation is rare compared to the device context's calls, so this is enabled from the
+// start to record how resources created after injection were created.
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "ID3D11Device",
+    source: HookSource::Custom(
+        "vtable pointer of the kernel device's ID3D11Device",
+        device_vtable_ptr,
+    ),
+    dependencies: &[],
+    enabled_by_default: true,
+    install,
+    stats: &STATS,
+}];
+
+unsafe fn device_vtable_ptr() -> anyhow::Result<*mut u8> {
+    use crate::game::graphics::kernel::Device;
+
+    Ok(super::vtable_ptr(Device::get().device()))
+}
+
+unsafe fn install(device_vtable_ptr: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    Ok(Box::new(super::VtablePatch {
+        patch_group: PATCH_GROUP,
+        vtable_ptr: device_vtable_ptr as *mut *const ID3D11DeviceVtbl,
+        original_vtable: std::ptr::addr_of_mut!(ORIGINAL_VTABLE),
+        hooked_vtable: &HOOKED_VTABLE,
+    }))
+}
@@ -1,2 +1,58 @@
+pub mod device;
 #[rustfmt::skip]
 pub mod device_context;
+pub mod swap_chain;
+
+use crate::hooks::{HookHandle, Patcher};
+
+use std::os::raw::c_void;
+
+/// The address of the vtable pointer of the COM object behind `interface`, which is the
+/// address of the object itself.
+unsafe fn vtable_ptr<Interface>(interface: &Interface) -> *mut u8 {
+    std::mem::transmute_copy(interface)
+}
+
+/// The object a method wrote to `out`, if it was asked to and succeeded.
+unsafe fn created_object(out: *mut *mut c_void) -> *mut c_void {
+    out.as_ref().copied().unwrap_or(std::ptr::null_mut())
+}
+
+/// Swaps a COM object's vtable pointer for a `com_interface!` `HOOKED_VTABLE` while
+/// enabled. The swapped pointer is tracked by the patcher, which won't restore it if it's
+/// been replaced by someone else in the meantime.
+struct VtablePatch<Vtbl: 'static> {
+    patch_group: &'static str,
+    vtable_ptr: *mut *const Vtbl,
+    original_vtable: *mut Option<*const Vtbl>,
+    hooked_vtable: &'static Vtbl,
+}
+
+impl<Vtbl: 'static> HookHandle for VtablePatch<Vtbl> {
+    unsafe fn enable(&mut self) -> anyhow::Result<()> {
+        let hooked_vtable = self.hooked_vtable as *const Vtbl;
+        if *self.vtable_ptr == hooked_vtable {
+            anyhow::bail!("{} is already patched", self.patch_group);
+        }
+
+        // The vtable may have changed since we were last enabled, so forward to whatever
+        // it is now.
+        *self.original_vtable = Some(*self.vtable_ptr);
+        let hooked_vtable_bytes = (hooked_vtable as usize).to_le_bytes();
+
+        let patcher =
+            Patcher::get_mut().ok_or_else(|| anyhow::Error::msg("Failed to retrieve patcher"))?;
+        patcher.apply(
+            self.patch_group,
+            &[(self.vtable_ptr as *mut u8, &hooked_vtable_bytes)],
+        )
+    }
+
+    unsafe fn disable(&mut self) -> anyhow::Result<()> {
+        // The original vtable is left alone, as other threads may still be inside our hooks.
+        if let Some(patcher) = Patcher::get_mut() {
+            patcher.unpatch(self.patch_group)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+#![allow(dead_code)]
+#![allow(non_snake_case)]
+
+use crate::debugger::d3d_payload::D3DPayload;
+use crate::debugger::Debugger;
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+
+use super::created_object;
+
+use windows::runtime::*;
+use windows::Win32::Foundation::{BOOL, HANDLE, HWND};
+use windows::Win32::Graphics::Dxgi::{
+    DXGI_COLOR_SPACE_TYPE, DXGI_FORMAT, DXGI_FRAME_STATISTICS, DXGI_HDR_METADATA_TYPE,
+    DXGI_MATRIX_3X2_F, DXGI_MODE_DESC, DXGI_MODE_ROTATION, DXGI_PRESENT_PARAMETERS, DXGI_RGBA,
+    DXGI_SWAP_CHAIN_DESC, DXGI_SWAP_CHAIN_DESC1, DXGI_SWAP_CHAIN_FULLSCREEN_DESC,
+};
+
+use std::os::raw::c_void;
+
+macros::com_interface!(IDXGISwapChain, {
+    payload: SwapChainPayload,
+    callback: push_back_payload,
+    stats: STATS,
+    methods: {
+        fn QueryInterface(riid: *const GUID, ppvObject: *mut *mut c_void) -> HRESULT;
+        fn AddRef() -> u32;
+        fn Release() -> u32;
+        fn SetPrivateData(Name: *const GUID, DataSize: u32, pData: *const c_void) -> HRESULT;
+        fn SetPrivateDataInterface(Name: *const GUID, pUnknown: *mut IUnknown) -> HRESULT;
+        fn GetPrivateData(Name: *const GUID, pDataSize: *mut u32, pData: *mut c_void) -> HRESULT;
+        fn GetParent(riid: *const GUID, ppParent: *mut *mut c_void) -> HRESULT;
+        fn GetDevice(riid: *const GUID, ppDevice: *mut *mut c_void) -> HRESULT;
+        fn Present(SyncInterval: u32, Flags: u32) -> HRESULT;
+        #[capture(surface: *mut c_void = created_object(ppSurface))]
+        fn GetBuffer(Buffer: u32, riid: *const GUID, ppSurface: *mut *mut c_void) -> HRESULT;
+        fn SetFullscreenState(Fullscreen: BOOL, pTarget: *mut c_void) -> HRESULT;
+        fn GetFullscreenState(pFullscreen: *mut BOOL, ppTarget: *mut *mut c_void) -> HRESULT;
+        fn GetDesc(pDesc: *mut DXGI_SWAP_CHAIN_DESC) -> HRESULT;
+        fn ResizeBuffers(BufferCount: u32, Width: u32, Height: u32, NewFormat: DXGI_FORMAT, SwapChainFlags: u32) -> HRESULT;
+        fn ResizeTarget(pNewTargetParameters: *const DXGI_MODE_DESC) -> HRESULT;
+        fn GetContainingOutput(ppOutput: *mut *mut c_void) -> HRESULT;
+        fn GetFrameStatistics(pStats: *mut DXGI_FRAME_STATISTICS) -> HRESULT;
+        fn GetLastPresentCount(pLastPresentCount: *mut u32) -> HRESULT;
+        fn GetDesc1(pDesc: *mut DXGI_SWAP_CHAIN_DESC1) -> HRESULT;
+        fn GetFullscreenDesc(pDesc: *mut DXGI_SWAP_CHAIN_FULLSCREEN_DESC) -> HRESULT;
+        fn GetHwnd(pHwnd: *mut HWND) -> HRESULT;
+        fn GetCoreWindow(refiid: *const GUID, ppUnk: *mut *mut c_void) -> HRESULT;
+        fn Present1(SyncInterval: u32, PresentFlags: u32, pPresentParameters: *const DXGI_PRESENT_PARAMETERS) -> HRESULT;
+        fn IsTemporaryMonoSupported() -> BOOL;
+        fn GetRestrictToOutput(ppRestrictToOutput: *mut *mut c_void) -> HRESULT;
+        fn SetBackgroundColor(pColor: *const DXGI_RGBA) -> HRESULT;
+        fn GetBackgroundColor(pColor: *mut DXGI_RGBA) -> HRESULT;
+        fn SetRotation(Rotation: DXGI_MODE_ROTATION) -> HRESULT;
+        fn GetRotation(pRotation: *mut DXGI_MODE_ROTATION) -> HRESULT;
+        fn SetSourceSize(Width: u32, Height: u32) -> HRESULT;
+        fn GetSourceSize(pWidth: *mut u32, pHeight: *mut u32) -> HRESULT;
+        fn SetMaximumFrameLatency(MaxLatency: u32) -> HRESULT;
+        fn GetMaximumFrameLatency(pMaxLatency: *mut u32) -> HRESULT;
+        fn GetFrameLatencyWaitableObject() -> HANDLE;
+        fn SetMatrixTransform(pMatrix: *const DXGI_MATRIX_3X2_F) -> HRESULT;
+        fn GetMatrixTransform(pMatrix: *mut DXGI_MATRIX_3X2_F) -> HRESULT;
+        fn GetCurrentBackBufferIndex() -> u32;
+        fn CheckColorSpaceSupport(ColorSpace: DXGI_COLOR_SPACE_TYPE, pColorSpaceSupport: *mut u32) -> HRESULT;
+        fn SetColorSpace1(ColorSpace: DXGI_COLOR_SPACE_TYPE) -> HRESULT;
+        fn ResizeBuffers1(BufferCount: u32, Width: u32, Height: u32, Format: DXGI_FORMAT, SwapChainFlags: u32, pCreationNodeMask: *const u32, ppPresentQueue: *const *mut c_void) -> HRESULT;
+        fn SetHDRMetaData(Type: DXGI_HDR_METADATA_TYPE, Size: u32, pMetaData: *const c_void) -> HRESULT;
+    },
+});
+
+fn push_back_payload(payload: SwapChainPayload) {
+    if let Some(debugger) = Debugger::get_mut() {
+        // As with the device, the debugger may call into the swap chain while holding
+        // the command stream, so calls made while it's locked are dropped.
+        if let Ok(mut command_stream) = debugger.command_stream.try_lock() {
+            command_stream
+                .add_d3d_command(D3DPayload::SwapChain(payload))
+                .unwrap();
+        }
+    }
+}
+
+static STATS: CallStats = CallStats::new();
+
+const PATCH_GROUP: &str = "dxgi_swap_chain_vtable";
+
+// Like the device context, this is only useful while capturing, so it's left disabled
+// until it's enabled from the debugger.
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "IDXGISwapChain",
+    source: HookSource::Custom(
+        "vtable pointer of the kernel swap chain's IDXGISwapChain",
+        swap_chain_vtable_ptr,
+    ),
+    dependencies: &[],
+    enabled_by_default: false,
+    install,
+    stats: &STATS,
+}];
+
+unsafe fn swap_chain_vtable_ptr() -> anyhow::Result<*mut u8> {
+    use crate::game::graphics::kernel::Device;
+
+    Ok(super::vtable_ptr(Device::get().swapchain().swapchain()))
+}
+
+unsafe fn install(swap_chain_vtable_ptr: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    Ok(Box::new(super::VtablePatch {
+        patch_group: PATCH_GROUP,
+        vtable_ptr: swap_chain_vtable_ptr as *mut *const IDXGISwapChainVtbl,
+        original_vtable: std::ptr::addr_of_mut!(ORIGINAL_VTABLE),
+        hooked_vtable: &HOOKED_VTABLE,
+    }))
+}
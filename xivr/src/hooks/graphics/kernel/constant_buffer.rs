@@ -1,6 +1,7 @@
 use crate::game::graphics::render;
 use crate::game::math;
-use crate::{log, util};
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::{signatures, util};
 
 use detour::static_detour;
 
@@ -44,20 +45,16 @@ static_detour! {
     pub static ConstantBuffer_LoadBuffer_Detour: fn(usize, usize, usize, *const BufferLoad);
 }
 
-pub struct HookState;
-
-impl Drop for HookState {
-    fn drop(&mut self) {
-        let res = unsafe { ConstantBuffer_LoadBuffer_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling loadbuffer hijack: {}",
-                e.to_string()
-            );
-        }
-    }
-}
+static LOAD_BUFFER_STATS: CallStats = CallStats::new();
+
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "ConstantBuffer::LoadBuffer",
+    source: HookSource::Signature(signatures::CONSTANT_BUFFER_LOAD_BUFFER),
+    dependencies: &[],
+    enabled_by_default: true,
+    install,
+    stats: &LOAD_BUFFER_STATS,
+}];
 
 unsafe fn loadbuffer_implementation(
     this: usize,
@@ -119,7 +116,8 @@ unsafe fn loadbuffer_implementation(
     CAMERA_PARAMETERS_PTRS.rotate_left(1);
     *CAMERA_PARAMETERS_PTRS.last_mut()? = Some(temp_buffer);
 
-    ConstantBuffer_LoadBuffer_Detour.call(this, unk1, unk2, new_load_ptr);
+    LOAD_BUFFER_STATS
+        .original(|| ConstantBuffer_LoadBuffer_Detour.call(this, unk1, unk2, new_load_ptr));
 
     Some(())
 }
@@ -156,22 +154,19 @@ fn constantbuffer_loadbuffer_hook(this: usize, unk1: usize, unk2: usize, load: *
             }
         }
 
-        ConstantBuffer_LoadBuffer_Detour.call(this, unk1, unk2, load);
+        LOAD_BUFFER_STATS
+            .original(|| ConstantBuffer_LoadBuffer_Detour.call(this, unk1, unk2, load));
         Ok(())
     });
 }
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    use std::mem;
-
-    let module = util::game_module_mut()?;
-    let constantbuffer_loadbuffer = module.scan("4C 89 44 24 ? 56 57 41 57")?;
-
+unsafe fn install(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
     ConstantBuffer_LoadBuffer_Detour.initialize(
-        mem::transmute(constantbuffer_loadbuffer),
-        constantbuffer_loadbuffer_hook,
+        std::mem::transmute(target),
+        |this, unk1, unk2, load| {
+            LOAD_BUFFER_STATS.call(|| constantbuffer_loadbuffer_hook(this, unk1, unk2, load))
+        },
     )?;
-    ConstantBuffer_LoadBuffer_Detour.enable()?;
 
-    Ok(HookState {})
+    Ok(Box::new(&ConstantBuffer_LoadBuffer_Detour))
 }
@@ -1,6 +1,7 @@
 use crate::debugger::Debugger;
 use crate::game::graphics::kernel::ShaderCommand;
-use crate::{log, util};
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::{signatures, util};
 
 use detour::static_detour;
 
@@ -8,19 +9,16 @@ static_detour! {
     pub static Context_PushBackCmd_Detour: fn(usize, &'static ShaderCommand) -> usize;
 }
 
-pub struct HookState;
-impl Drop for HookState {
-    fn drop(&mut self) {
-        let res = unsafe { Context_PushBackCmd_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling context detour: {}",
-                e.to_string()
-            );
-        }
-    }
-}
+static PUSH_BACK_CMD_STATS: CallStats = CallStats::new();
+
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "Context::PushBackCmd",
+    source: HookSource::Signature(signatures::CONTEXT_PUSH_BACK_COMMAND),
+    dependencies: &[],
+    enabled_by_default: true,
+    install,
+    stats: &PUSH_BACK_CMD_STATS,
+}];
 
 fn context_pushbackcmd_hook(ctx: usize, cmd: &'static ShaderCommand) -> usize {
     util::handle_error_in_block(|| {
@@ -31,18 +29,16 @@ fn context_pushbackcmd_hook(ctx: usize, cmd: &'static ShaderCommand) -> usize {
         }
         Ok(())
     });
-    Context_PushBackCmd_Detour.call(ctx, cmd)
+    PUSH_BACK_CMD_STATS.original(|| Context_PushBackCmd_Detour.call(ctx, cmd))
 }
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    use std::mem;
-
-    let module = util::game_module_mut()?;
+unsafe fn install(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
     let context_pushbackcmd: fn(usize, &'static ShaderCommand) -> usize =
-        mem::transmute(module.scan("83 41 30 FF")?);
+        std::mem::transmute(target);
 
-    Context_PushBackCmd_Detour.initialize(context_pushbackcmd, context_pushbackcmd_hook)?;
-    Context_PushBackCmd_Detour.enable()?;
+    Context_PushBackCmd_Detour.initialize(context_pushbackcmd, |ctx, cmd| {
+        PUSH_BACK_CMD_STATS.call(|| context_pushbackcmd_hook(ctx, cmd))
+    })?;
 
-    Ok(HookState {})
+    Ok(Box::new(&Context_PushBackCmd_Detour))
 }
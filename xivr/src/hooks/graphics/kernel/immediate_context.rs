@@ -1,162 +1,254 @@
 use crate::ct_config::rendering::SHADER_COMMAND_HIJACKED_TYPE;
 use crate::game::graphics::kernel::{ImmediateContext, ShaderCommand};
-use crate::{hooks, log, util};
-use detour::{static_detour, RawDetour};
-use std::arch::global_asm;
+use crate::hooks::jump_table::{JumpTable, JumpTableHijack, Register};
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::{signatures, util};
+use detour::static_detour;
 
-#[no_mangle]
-static mut PROCESS_COMMANDS_DEFAULT_CASE: *mut u8 = std::ptr::null_mut();
-const PROCESS_COMMANDS_TABLE_LENGTH: u32 = 18;
+use std::cell::Cell;
+
+// `cmp eax, imm8; ja default`: the bounds check on the command type before the switch.
+const PROCESS_COMMANDS_SWITCH_PATTERN: &str = "83 F8 <max_type:u8> 0F 87";
 
 static_detour! {
     pub static ImmediateContext_ProcessCommands_Detour: fn(&'static ImmediateContext, u64, u32) -> u64;
 }
 
-#[allow(dead_code)]
-pub enum XIVRCommandPayload {
-    None,
-    Integer(u32),
-    Address(*const u8),
+/// Which of the passes over the game's command list is being processed. While XR is
+/// active, the list is processed once per view; otherwise, there's only the one pass.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CommandPass {
+    pub index: u32,
+    pub count: u32,
+}
+
+impl CommandPass {
+    const SINGLE: CommandPass = CommandPass { index: 0, count: 1 };
+
+    pub fn is_last(&self) -> bool {
+        self.index + 1 >= self.count
+    }
+}
+
+thread_local! {
+    static CURRENT_PASS: Cell<CommandPass> = Cell::new(CommandPass::SINGLE);
+}
+
+/// Work to do on the render thread, at the point in the game's command stream that it was
+/// pushed to with `Context::push_back_xivr_command`.
+///
+/// A command is executed on every pass over the command list, so that it can make
+/// per-view state changes, and is only dropped after the last pass.
+pub trait XIVRCommand: Send + 'static {
+    fn execute(&mut self, context: &'static ImmediateContext, pass: CommandPass);
+}
+
+impl<F: FnMut(&'static ImmediateContext, CommandPass) + Send + 'static> XIVRCommand for F {
+    fn execute(&mut self, context: &'static ImmediateContext, pass: CommandPass) {
+        self(context, pass)
+    }
 }
 
+/// The start of every XIVR command, which is all the jump table handler knows about.
+/// `execute` is instantiated for the concrete command, which follows the header.
 #[repr(C)]
-pub struct XIVRCommand {
+pub struct XIVRCommandHeader {
     cmd_type: u32,
-    callback: fn(context: &'static ImmediateContext, &XIVRCommandPayload) -> (),
-    payload: XIVRCommandPayload,
+    execute: unsafe fn(context: &'static ImmediateContext, header: *mut XIVRCommandHeader),
+    drop: unsafe fn(header: *mut XIVRCommandHeader),
+}
+
+#[repr(C)]
+pub struct XIVRCommandData<C: XIVRCommand> {
+    header: XIVRCommandHeader,
+    command: C,
 }
 
-#[allow(dead_code)]
-impl XIVRCommand {
-    pub fn new(
-        callback: fn(context: &'static ImmediateContext, &XIVRCommandPayload) -> (),
-        payload: XIVRCommandPayload,
-    ) -> XIVRCommand {
-        XIVRCommand {
-            cmd_type: SHADER_COMMAND_HIJACKED_TYPE as u32,
-            callback,
-            payload,
+impl<C: XIVRCommand> XIVRCommandData<C> {
+    pub fn new(command: C) -> XIVRCommandData<C> {
+        XIVRCommandData {
+            header: XIVRCommandHeader {
+                cmd_type: SHADER_COMMAND_HIJACKED_TYPE as u32,
+                execute: Self::execute,
+                drop: Self::drop,
+            },
+            command,
         }
     }
-}
 
-pub struct HookState(detour::RawDetour);
-impl Drop for HookState {
-    fn drop(&mut self) {
-        let res = unsafe { self.0.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling immediate context detour: {}",
-                e.to_string()
-            )
+    /// Runs the command in place, and drops it after the last pass. The allocation itself
+    /// belongs to the game.
+    unsafe fn execute(context: &'static ImmediateContext, header: *mut XIVRCommandHeader) {
+        let command = std::ptr::addr_of_mut!((*(header as *mut XIVRCommandData<C>)).command);
+        let pass = CURRENT_PASS.with(Cell::get);
+        (*command).execute(context, pass);
+        if pass.is_last() {
+            Self::drop(header);
         }
-        let res = unsafe { ImmediateContext_ProcessCommands_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling process commands detour: {}",
-                e.to_string()
-            )
+    }
+
+    unsafe fn drop(header: *mut XIVRCommandHeader) {
+        std::ptr::drop_in_place(std::ptr::addr_of_mut!(
+            (*(header as *mut XIVRCommandData<C>)).command
+        ));
+    }
+}
+
+/// An entry in the list of commands given to `ProcessCommands`.
+#[repr(C)]
+struct StreamCommand {
+    #[allow(dead_code)]
+    sort_key: u64,
+    cmd: *mut ShaderCommand,
+}
+
+/// Drops the XIVR commands in `commands` without executing them, for when the passes over
+/// the list are cut short and the last pass, which would have dropped them, doesn't happen.
+unsafe fn drop_xivr_commands(commands: &[StreamCommand]) {
+    for command in commands {
+        let header = command.cmd as *mut XIVRCommandHeader;
+        if (*header).cmd_type == SHADER_COMMAND_HIJACKED_TYPE as u32 {
+            ((*header).drop)(header);
         }
     }
 }
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    let module = util::game_module_mut()?;
-    let process_commands =
-        module.scan_for_relative_callsite("E8 ? ? ? ? 48 8B 4B 30 FF 15 ? ? ? ?")?;
+/// Whether XIVR commands will be executed. If they're pushed while they won't be, the game
+/// skips them, and their payloads are never dropped.
+pub fn xivr_commands_enabled() -> bool {
+    crate::hooks::Patcher::get().map_or(false, |p| p.is_patched(PATCH_GROUP))
+}
 
+const PATCH_GROUP: &str = "immediate_context_jump_table";
+
+static PROCESS_COMMANDS_STATS: CallStats = CallStats::new();
+static XIVR_COMMANDS_STATS: CallStats = CallStats::new();
+
+pub static HOOKS: &[Hook] = &[
+    Hook {
+        name: "ImmediateContext::ProcessCommands",
+        source: HookSource::Signature(signatures::IMMEDIATE_CONTEXT_PROCESS_COMMANDS),
+        dependencies: &[],
+        enabled_by_default: true,
+        install: install_process_commands,
+        stats: &PROCESS_COMMANDS_STATS,
+    },
+    Hook {
+        name: "ImmediateContext::XIVRCommands",
+        source: HookSource::Signature(signatures::IMMEDIATE_CONTEXT_PROCESS_COMMANDS),
+        dependencies: &[],
+        enabled_by_default: true,
+        install: install_jump_table,
+        stats: &XIVR_COMMANDS_STATS,
+    },
+];
+
+unsafe fn install_process_commands(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
     ImmediateContext_ProcessCommands_Detour.initialize(
-        std::mem::transmute(process_commands),
+        std::mem::transmute(target),
         |ic, a2, command_count| {
-            util::handle_error_in_block(|| {
-                use crate::debugger::Debugger;
-                use crate::xr::XR;
-
-                #[repr(C)]
-                struct StreamCommand {
-                    sort_key: u64,
-                    cmd: *mut ShaderCommand,
-                }
-
-                let p = a2 as *mut StreamCommand;
-
-                if let Some(debugger) = Debugger::get_mut() {
-                    if let Ok(mut command_stream) = debugger.command_stream.lock() {
-                        if command_stream.is_capturing() {
-                            for i in 0..command_count {
-                                let stream_cmd: &StreamCommand = &*p.add(i as usize);
-                                let cmd: &ShaderCommand = &*stream_cmd.cmd;
-
-                                command_stream.add_processed_command(cmd)?;
+            PROCESS_COMMANDS_STATS.call(|| {
+                util::handle_error_in_block(|| {
+                    use crate::debugger::Debugger;
+                    use crate::xr::XR;
+
+                    let commands = std::slice::from_raw_parts(
+                        a2 as *const StreamCommand,
+                        command_count as usize,
+                    );
+                    // The last pass over the list drops the XIVR commands in it. If the passes
+                    // are cut short by an error, they're dropped here instead.
+                    let mut last_pass_done = false;
+                    let result = (|| -> anyhow::Result<()> {
+                        if let Some(debugger) = Debugger::get_mut() {
+                            if let Ok(mut command_stream) = debugger.command_stream.lock() {
+                                if command_stream.is_capturing() {
+                                    for command in commands {
+                                        command_stream.add_processed_command(&*command.cmd)?;
+                                    }
+                                }
                             }
                         }
-                    }
-                }
 
-                if let Some(xr) = XR::get_mut() {
-                    xr.pre_render()?;
-                    for i in 0..2 {
-                        ImmediateContext_ProcessCommands_Detour.call(ic, a2, command_count);
-                        xr.copy_backbuffer_to_buffer(i)?;
+                        if let Some(xr) = XR::get_mut() {
+                            const PASSES: u32 = 2;
+
+                            xr.pre_render()?;
+                            for i in 0..PASSES {
+                                let pass = CommandPass {
+                                    index: i,
+                                    count: PASSES,
+                                };
+                                CURRENT_PASS.with(|current| current.set(pass));
+                                PROCESS_COMMANDS_STATS.original(|| {
+                                    ImmediateContext_ProcessCommands_Detour.call(
+                                        ic,
+                                        a2,
+                                        command_count,
+                                    )
+                                });
+                                CURRENT_PASS.with(|current| current.set(CommandPass::SINGLE));
+                                last_pass_done = pass.is_last();
+                                xr.copy_backbuffer_to_buffer(i)?;
+                            }
+                            xr.post_render()?;
+                        } else {
+                            PROCESS_COMMANDS_STATS.original(|| {
+                                ImmediateContext_ProcessCommands_Detour.call(ic, a2, command_count)
+                            });
+                            last_pass_done = true;
+                        }
+                        Ok(())
+                    })();
+                    if !last_pass_done {
+                        drop_xivr_commands(commands);
                     }
-                    xr.post_render()?;
-                } else {
-                    ImmediateContext_ProcessCommands_Detour.call(ic, a2, command_count);
-                }
-                Ok(0u64)
+                    result?;
+
+                    Ok(0u64)
+                })
             })
         },
     )?;
-    ImmediateContext_ProcessCommands_Detour.enable()?;
 
-    let padding = module.scan_after_ptr(process_commands, &"CC ".repeat(10))?;
-    let padding_detour = RawDetour::new(
-        padding as *const (),
-        process_commands_jump_trampoline as *const (),
-    )?;
-    padding_detour.enable()?;
-
-    let jump_table_slice = {
-        let jump_table = padding.offset(-(PROCESS_COMMANDS_TABLE_LENGTH as isize) * 4);
-        std::slice::from_raw_parts_mut(
-            jump_table as *mut u32,
-            PROCESS_COMMANDS_TABLE_LENGTH as usize,
-        )
-    };
+    Ok(Box::new(&ImmediateContext_ProcessCommands_Detour))
+}
+
+unsafe fn install_jump_table(process_commands: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    let module = util::game_module_mut()?;
 
-    let default_offset = jump_table_slice[SHADER_COMMAND_HIJACKED_TYPE] as usize;
-    PROCESS_COMMANDS_DEFAULT_CASE = module.rel_to_abs_addr(default_offset);
+    let switch =
+        module.scan_after_ptr_captures(process_commands, PROCESS_COMMANDS_SWITCH_PATTERN)?;
+    let process_commands_bounds = module
+        .function_bounds(module.abs_to_rel_addr(process_commands) as usize)?
+        .ok_or_else(|| anyhow::Error::msg("Failed to find bounds of ProcessCommands"))?;
+    if !process_commands_bounds.contains(&switch.match_offset) {
+        anyhow::bail!("Failed to find command type switch in ProcessCommands");
+    }
+    let table_length = switch.get("max_type")? + 1;
 
-    let padding_rel = module.abs_to_rel_addr(padding) as i32;
-    hooks::Patcher::get_mut()
-        .ok_or_else(|| anyhow::Error::msg("Failed to retrieve patcher"))?
-        .patch(
-            (&mut jump_table_slice[SHADER_COMMAND_HIJACKED_TYPE]) as *mut _ as *mut u8,
-            &padding_rel.to_ne_bytes(),
-        );
+    // The jump table is laid out directly after the function, before the padding.
+    let padding = module.scan_after_ptr(process_commands, &"CC ".repeat(10))?;
+    let jump_table = JumpTable {
+        function: process_commands,
+        table: padding.offset(-(table_length as isize) * 4) as *mut u32,
+        len: table_length,
+    };
 
-    Ok(HookState(padding_detour))
+    // The switch holds the context in rbx and the command in r10.
+    Ok(Box::new(JumpTableHijack::new(
+        module,
+        PATCH_GROUP,
+        jump_table,
+        SHADER_COMMAND_HIJACKED_TYPE,
+        &[Register::Rbx, Register::R10],
+        process_xivr_command as *const (),
+    )?))
 }
 
-extern "C" {
-    fn process_commands_jump_trampoline();
-}
-global_asm!(
-    ".global process_commands_jump_trampoline",
-    "process_commands_jump_trampoline:",
-    "   MOV rdx, r10",
-    "   MOV rcx, rbx",
-    "   MOVABS rax, PROCESS_COMMANDS_DEFAULT_CASE",
-    "   PUSH rax",
-    "   JMP process_commands_jump_table_9",
-);
-
-#[no_mangle]
-unsafe extern "C" fn process_commands_jump_table_9(
+unsafe extern "C" fn process_xivr_command(
     context: &'static ImmediateContext,
-    cmd: *const XIVRCommand,
+    header: *mut XIVRCommandHeader,
 ) {
-    ((*cmd).callback)(context, &(*cmd).payload);
+    XIVR_COMMANDS_STATS.call(|| ((*header).execute)(context, header));
 }
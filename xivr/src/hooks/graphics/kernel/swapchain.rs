@@ -1,6 +1,7 @@
 // E8 ? ? ? ? C6 83 ? ? ? ? ? 48 8B 4B 70
 
-use crate::log;
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::signatures;
 
 use detour::static_detour;
 
@@ -8,19 +9,16 @@ static_detour! {
     pub static Swapchain_Present_Detour: fn(usize);
 }
 
-pub struct HookState;
-impl Drop for HookState {
-    fn drop(&mut self) {
-        let res = unsafe { Swapchain_Present_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling swapchain detour: {}",
-                e.to_string()
-            );
-        }
-    }
-}
+static PRESENT_STATS: CallStats = CallStats::new();
+
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "Swapchain::Present",
+    source: HookSource::Signature(signatures::SWAPCHAIN_PRESENT),
+    dependencies: &[],
+    enabled_by_default: true,
+    install,
+    stats: &PRESENT_STATS,
+}];
 
 fn swapchain_present_hook(swapchain: usize) {
     crate::util::handle_error_in_block(|| {
@@ -31,19 +29,15 @@ fn swapchain_present_hook(swapchain: usize) {
         Ok(())
     });
 
-    Swapchain_Present_Detour.call(swapchain);
+    PRESENT_STATS.original(|| Swapchain_Present_Detour.call(swapchain));
 }
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    use std::mem;
-
-    let module = crate::util::game_module_mut()?;
-    let swapchain_present: fn(usize) = mem::transmute(
-        module.scan_for_relative_callsite("E8 ? ? ? ? C6 83 ? ? ? ? ? 48 8B 4B 70")?,
-    );
+unsafe fn install(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    let swapchain_present: fn(usize) = std::mem::transmute(target);
 
-    Swapchain_Present_Detour.initialize(swapchain_present, swapchain_present_hook)?;
-    Swapchain_Present_Detour.enable()?;
+    Swapchain_Present_Detour.initialize(swapchain_present, |swapchain| {
+        PRESENT_STATS.call(|| swapchain_present_hook(swapchain))
+    })?;
 
-    Ok(HookState {})
+    Ok(Box::new(&Swapchain_Present_Detour))
 }
@@ -1,5 +1,6 @@
 use crate::game::graphics::render;
-use crate::util;
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::{signatures, util};
 
 use detour::static_detour;
 
@@ -7,35 +8,26 @@ static_detour! {
     pub static Camera_UpdateConstantBuffers_Detour: fn(*mut render::Camera) -> usize;
 }
 
-pub struct HookState;
-impl Drop for HookState {
-    fn drop(&mut self) {
-        use crate::log;
-
-        let res = unsafe { Camera_UpdateConstantBuffers_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling constant buffers detour: {}",
-                e.to_string()
-            )
-        }
-    }
-}
+static UPDATE_CONSTANT_BUFFERS_STATS: CallStats = CallStats::new();
+
+// Hands the render camera over to the constant buffer hook, which is useless without it.
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "Camera::UpdateConstantBuffers",
+    source: HookSource::Signature(signatures::CAMERA_UPDATE_CONSTANT_BUFFERS),
+    dependencies: &["ConstantBuffer::LoadBuffer"],
+    enabled_by_default: true,
+    install,
+    stats: &UPDATE_CONSTANT_BUFFERS_STATS,
+}];
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
+unsafe fn install(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
     use crate::hooks::graphics::kernel::constant_buffer;
     use std::mem;
 
-    let module = util::game_module_mut()?;
-    let camera_updateconstantbuffers_addr =
-        module.scan_for_relative_callsite("E8 ? ? ? ? E9 ? ? ? ? 42 83 64 37 ? ?")?;
-    constant_buffer::RENDER_CAMERA_UPDATE_CONSTANT_BUFFERS_PTR =
-        Some(camera_updateconstantbuffers_addr);
+    constant_buffer::RENDER_CAMERA_UPDATE_CONSTANT_BUFFERS_PTR = Some(target);
 
-    Camera_UpdateConstantBuffers_Detour.initialize(
-        mem::transmute(camera_updateconstantbuffers_addr),
-        move |s| {
+    Camera_UpdateConstantBuffers_Detour.initialize(mem::transmute(target), move |s| {
+        UPDATE_CONSTANT_BUFFERS_STATS.call(|| {
             util::handle_error_in_block(|| {
                 if s == *render::RenderManager::get().render_camera() {
                     // The render camera is rendering. Let's back up its state to our friendly
@@ -44,12 +36,12 @@ pub unsafe fn install() -> anyhow::Result<HookState> {
                         constant_buffer::RENDER_CAMERA_BUFFER = Some((*s).clone());
                     }
                 }
-                Camera_UpdateConstantBuffers_Detour.call(s);
+                UPDATE_CONSTANT_BUFFERS_STATS
+                    .original(|| Camera_UpdateConstantBuffers_Detour.call(s));
                 Ok(0usize)
             })
-        },
-    )?;
-    Camera_UpdateConstantBuffers_Detour.enable()?;
+        })
+    })?;
 
-    Ok(HookState {})
+    Ok(Box::new(&Camera_UpdateConstantBuffers_Detour))
 }
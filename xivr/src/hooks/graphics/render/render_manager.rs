@@ -1,5 +1,7 @@
 use crate::ct_config::*;
-use crate::util;
+use crate::hooks::graphics::kernel::immediate_context::{xivr_commands_enabled, CommandPass};
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
+use crate::{signatures, util};
 
 use detour::static_detour;
 
@@ -8,39 +10,51 @@ static_detour! {
     pub static RenderManager_RenderUI_Detour: fn(usize, u8) -> usize;
 }
 
-pub struct HookState;
-impl Drop for HookState {
-    fn drop(&mut self) {
-        use crate::log;
+static RENDER_STATS: CallStats = CallStats::new();
+static RENDER_UI_STATS: CallStats = CallStats::new();
 
-        let res = unsafe { RenderManager_Render_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling render detour: {}",
-                e.to_string()
-            )
-        }
-        let res = unsafe { RenderManager_RenderUI_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling renderui detour: {}",
-                e.to_string()
-            )
-        }
-    }
-}
+pub static HOOKS: &[Hook] = &[
+    Hook {
+        name: "RenderManager::Render",
+        source: HookSource::Signature(signatures::RENDER_MANAGER_RENDER),
+        dependencies: &[],
+        enabled_by_default: true,
+        install: install_render,
+        stats: &RENDER_STATS,
+    },
+    Hook {
+        name: "RenderManager::RenderUI",
+        source: HookSource::Signature(signatures::RENDER_MANAGER_RENDER_UI),
+        dependencies: &[],
+        enabled_by_default: true,
+        install: install_render_ui,
+        stats: &RENDER_UI_STATS,
+    },
+];
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    use std::mem;
+/// Records `msg` in the debugger's message stream when the render thread gets to this
+/// point in the command stream, once for each pass over it.
+fn push_render_thread_marker(msg: String) -> anyhow::Result<()> {
+    use crate::debugger::Debugger;
+    use crate::game::graphics::kernel::{Context, ImmediateContext};
 
-    let module = util::game_module_mut()?;
-    let rendermanager_render_addr = module.scan("40 53 55 57 41 56 41 57 48 83 EC 60")?;
+    Context::get_for_current_thread()?.push_back_xivr_command(
+        move |_: &'static ImmediateContext, pass: CommandPass| {
+            if let Some(debugger) = Debugger::get_mut() {
+                if let Ok(mut command_stream) = debugger.command_stream.lock() {
+                    util::handle_error(command_stream.add_message(
+                        msg.clone(),
+                        vec![format!("pass {} of {}", pass.index + 1, pass.count)],
+                    ));
+                }
+            }
+        },
+    )
+}
 
-    RenderManager_Render_Detour.initialize(
-        mem::transmute(rendermanager_render_addr),
-        move |s| {
+unsafe fn install_render(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    RenderManager_Render_Detour.initialize(std::mem::transmute(target), move |s| {
+        RENDER_STATS.call(|| {
             util::handle_error_in_block(|| {
                 if rendering::DISABLE_GAME {
                     return Ok(0usize);
@@ -50,9 +64,12 @@ pub unsafe fn install() -> anyhow::Result<HookState> {
                 if let Some(debugger) = Debugger::get_mut() {
                     if let Ok(mut command_stream) = debugger.command_stream.lock() {
                         command_stream.add_marker("RenderManager::Render pre-call".to_owned())?;
+                        if command_stream.is_capturing() && xivr_commands_enabled() {
+                            push_render_thread_marker("RenderManager::Render".to_owned())?;
+                        }
                     }
                 }
-                RenderManager_Render_Detour.call(s);
+                RENDER_STATS.original(|| RenderManager_Render_Detour.call(s));
                 if let Some(debugger) = Debugger::get_mut() {
                     if let Ok(mut command_stream) = debugger.command_stream.lock() {
                         command_stream.add_marker("RenderManager::Render post-call".to_owned())?;
@@ -61,16 +78,15 @@ pub unsafe fn install() -> anyhow::Result<HookState> {
 
                 Ok(0usize)
             })
-        },
-    )?;
-    RenderManager_Render_Detour.enable()?;
+        })
+    })?;
 
-    let rendermanager_renderui_addr = module
-        .scan("48 89 5C 24 ? 48 89 6C 24 ? 56 57 41 54 41 56 41 57 48 83 EC 40 44 8B 05 ? ? ? ?")?;
+    Ok(Box::new(&RenderManager_Render_Detour))
+}
 
-    RenderManager_RenderUI_Detour.initialize(
-        mem::transmute(rendermanager_renderui_addr),
-        move |s, a| {
+unsafe fn install_render_ui(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    RenderManager_RenderUI_Detour.initialize(std::mem::transmute(target), move |s, a| {
+        RENDER_UI_STATS.call(|| {
             util::handle_error_in_block(|| {
                 if rendering::DISABLE_UI {
                     return Ok(0usize);
@@ -82,7 +98,7 @@ pub unsafe fn install() -> anyhow::Result<HookState> {
                         command_stream.add_marker("RenderManager::RenderUI pre-call".to_owned())?;
                     }
                 }
-                let ret = RenderManager_RenderUI_Detour.call(s, a);
+                let ret = RENDER_UI_STATS.original(|| RenderManager_RenderUI_Detour.call(s, a));
                 if let Some(debugger) = Debugger::get_mut() {
                     if let Ok(mut command_stream) = debugger.command_stream.lock() {
                         command_stream
@@ -92,9 +108,8 @@ pub unsafe fn install() -> anyhow::Result<HookState> {
 
                 Ok(ret)
             })
-        },
-    )?;
-    RenderManager_RenderUI_Detour.enable()?;
+        })
+    })?;
 
-    Ok(HookState {})
+    Ok(Box::new(&RenderManager_RenderUI_Detour))
 }
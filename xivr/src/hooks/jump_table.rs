@@ -0,0 +1,241 @@
+use crate::hooks::{HookHandle, Patcher};
+use crate::log;
+
+use module::Module;
+
+use once_cell::sync::Lazy;
+
+use std::convert::TryInto;
+use std::os::raw::c_void;
+use std::sync::Mutex;
+
+use windows::Win32::System::Memory::{
+    VirtualAlloc, VirtualFree, MEM_COMMIT, MEM_RELEASE, MEM_RESERVE, PAGE_EXECUTE_READWRITE,
+};
+
+/// A general-purpose register, numbered as it's encoded in x64 instructions. `rsp` is
+/// left out, as the trampoline moves it before the handler would see it.
+#[allow(dead_code)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Register {
+    Rax = 0,
+    Rcx = 1,
+    Rdx = 2,
+    Rbx = 3,
+    Rbp = 5,
+    Rsi = 6,
+    Rdi = 7,
+    R8 = 8,
+    R9 = 9,
+    R10 = 10,
+    R11 = 11,
+    R12 = 12,
+    R13 = 13,
+    R14 = 14,
+    R15 = 15,
+}
+
+/// The registers the first four integer arguments are passed in.
+const ARGUMENT_REGISTERS: [Register; 4] =
+    [Register::Rcx, Register::Rdx, Register::R8, Register::R9];
+
+/// A switch's jump table, as emitted by MSVC: an array of `u32` offsets from the module
+/// base, each of which points at a case in `function`.
+pub struct JumpTable {
+    pub function: *mut u8,
+    pub table: *mut u32,
+    pub len: usize,
+}
+
+/// Executable memory within 4GB after the start of a module, so that it can be reached
+/// from a jump table entry. Freed when dropped.
+struct Trampoline {
+    address: *mut u8,
+}
+unsafe impl Send for Trampoline {}
+
+/// Trampolines whose jump table entries have been restored. A thread that read the entry
+/// before it was restored can still be on its way through the trampoline, so they're
+/// kept until `free_retired_trampolines` is called once no thread can be.
+static RETIRED_TRAMPOLINES: Lazy<Mutex<Vec<Trampoline>>> = Lazy::new(|| Mutex::new(vec![]));
+
+/// Frees the trampolines of every hijack that has been dropped. Must only be called once
+/// every thread has left our hooks, e.g. after `hooks::wait_until_idle` when unloading.
+pub fn free_retired_trampolines() {
+    RETIRED_TRAMPOLINES.lock().unwrap().clear();
+}
+
+impl Trampoline {
+    const ALLOCATION_GRANULARITY: usize = 0x10000;
+
+    unsafe fn allocate_after(module: &Module, code: &[u8]) -> anyhow::Result<Trampoline> {
+        let base = module.base as usize;
+        let image_end = base + module.as_bytes().len();
+        let mut candidate =
+            (image_end + Self::ALLOCATION_GRANULARITY - 1) & !(Self::ALLOCATION_GRANULARITY - 1);
+
+        while candidate + code.len() - base <= u32::MAX as usize {
+            let address = VirtualAlloc(
+                candidate as *const c_void,
+                code.len(),
+                MEM_COMMIT | MEM_RESERVE,
+                PAGE_EXECUTE_READWRITE,
+            ) as *mut u8;
+            if !address.is_null() {
+                std::ptr::copy_nonoverlapping(code.as_ptr(), address, code.len());
+                return Ok(Trampoline { address });
+            }
+            candidate += Self::ALLOCATION_GRANULARITY;
+        }
+
+        anyhow::bail!("Failed to allocate a trampoline within reach of the jump table")
+    }
+}
+
+impl Drop for Trampoline {
+    fn drop(&mut self) {
+        unsafe { VirtualFree(self.address as *mut c_void, 0, MEM_RELEASE) };
+    }
+}
+
+/// Assembles code that moves `arguments` into the argument registers, pushes
+/// `return_address` and jumps to `handler`, so that the handler returns to it as if it
+/// had been called from there.
+fn assemble_trampoline(arguments: &[Register], return_address: usize, handler: usize) -> Vec<u8> {
+    fn rex_b(register: Register) -> Option<u8> {
+        (register as u8 >= 8).then(|| 0x41)
+    }
+
+    let mut code = vec![];
+    // Going through the stack means the mapping can be any permutation of registers.
+    for &register in arguments {
+        code.extend(rex_b(register));
+        code.push(0x50 + (register as u8 & 7)); // PUSH register
+    }
+    for &register in ARGUMENT_REGISTERS[..arguments.len()].iter().rev() {
+        code.extend(rex_b(register));
+        code.push(0x58 + (register as u8 & 7)); // POP register
+    }
+    code.extend([0x48, 0xB8]); // MOVABS rax, return_address
+    code.extend(return_address.to_le_bytes());
+    code.push(0x50); // PUSH rax
+    code.extend([0x48, 0xB8]); // MOVABS rax, handler
+    code.extend(handler.to_le_bytes());
+    code.extend([0xFF, 0xE0]); // JMP rax
+    code
+}
+
+/// Sends one case of a jump table to a trampoline that calls a handler, which then
+/// returns to the case's original code. The entry is only redirected while enabled.
+///
+/// The handler clobbers the volatile registers, so the case's code must not rely on them.
+pub struct JumpTableHijack {
+    patch_group: &'static str,
+    entry: *mut u32,
+    trampoline_rel: u32,
+    trampoline: Option<Trampoline>,
+}
+
+impl JumpTableHijack {
+    /// Validates that `table` lies within `module` and that all of its entries point into
+    /// its function, then builds a trampoline for entry `case` that passes `arguments`
+    /// (the registers the switch holds them in) as the first arguments of `handler`.
+    pub unsafe fn new(
+        module: &Module,
+        patch_group: &'static str,
+        table: JumpTable,
+        case: usize,
+        arguments: &[Register],
+        handler: *const (),
+    ) -> anyhow::Result<JumpTableHijack> {
+        if arguments.len() > ARGUMENT_REGISTERS.len() {
+            anyhow::bail!(
+                "Jump table handlers can take at most {} arguments",
+                ARGUMENT_REGISTERS.len()
+            );
+        }
+        if case >= table.len {
+            anyhow::bail!(
+                "Case {} is outside of the jump table ({} entries)",
+                case,
+                table.len
+            );
+        }
+
+        let table_start = module.abs_to_rel_addr(table.table as *const u8);
+        let table_end = table_start + (table.len * std::mem::size_of::<u32>()) as isize;
+        if table_start < 0 || table_end as usize > module.as_bytes().len() {
+            anyhow::bail!("Jump table at {:X?} is outside of the module", table.table);
+        }
+
+        // Cases can be in chunks split off from the function, so each one is looked up in
+        // the function table instead of being compared against the function's range.
+        let functions = module.function_table()?;
+        let function_bounds = functions
+            .bounds(module.abs_to_rel_addr(table.function) as usize)
+            .ok_or_else(|| anyhow::Error::msg("Failed to find bounds of jump table function"))?;
+        let entries = std::slice::from_raw_parts_mut(table.table, table.len);
+        for offset in entries.iter() {
+            if functions.bounds(*offset as usize).as_ref() != Some(&function_bounds) {
+                anyhow::bail!(
+                    "Jump table at {:X?} points outside of its function",
+                    table.table
+                );
+            }
+        }
+
+        let original_case = module.rel_to_abs_addr(entries[case] as usize);
+        let code = assemble_trampoline(arguments, original_case as usize, handler as usize);
+        let trampoline = Trampoline::allocate_after(module, &code)?;
+        let trampoline_rel = module.abs_to_rel_addr(trampoline.address).try_into()?;
+
+        Ok(JumpTableHijack {
+            patch_group,
+            entry: &mut entries[case],
+            trampoline_rel,
+            trampoline: Some(trampoline),
+        })
+    }
+}
+
+impl HookHandle for JumpTableHijack {
+    unsafe fn enable(&mut self) -> anyhow::Result<()> {
+        let patcher =
+            Patcher::get_mut().ok_or_else(|| anyhow::Error::msg("Failed to retrieve patcher"))?;
+        patcher.apply(
+            self.patch_group,
+            &[(self.entry as *mut u8, &self.trampoline_rel.to_le_bytes())],
+        )
+    }
+
+    unsafe fn disable(&mut self) -> anyhow::Result<()> {
+        if let Some(patcher) = Patcher::get_mut() {
+            patcher.unpatch(self.patch_group)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for JumpTableHijack {
+    fn drop(&mut self) {
+        unsafe {
+            if Patcher::get().map_or(false, |p| p.is_patched(self.patch_group)) {
+                if let Err(e) = self.disable() {
+                    log!("error", "error while restoring jump table: {}", e);
+                }
+            }
+
+            // If the entry couldn't be restored, the trampoline has to outlive us.
+            if *self.entry == self.trampoline_rel {
+                log!(
+                    "error",
+                    "leaking trampoline for {}, as the jump table still uses it",
+                    self.patch_group
+                );
+                std::mem::forget(self.trampoline.take());
+            } else if let Some(trampoline) = self.trampoline.take() {
+                RETIRED_TRAMPOLINES.lock().unwrap().push(trampoline);
+            }
+        }
+    }
+}
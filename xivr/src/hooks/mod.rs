@@ -1,22 +1,55 @@
+pub mod jump_table;
+
 pub mod patcher;
 pub use patcher::*;
 
+pub mod registry;
+pub use registry::*;
+
+pub mod stats;
+pub use stats::*;
+
 pub mod graphics;
 pub mod system;
 
-use crate::singleton;
+use std::time::{Duration, Instant};
 
-pub struct HookState {
-    _system: system::HookState,
-    _graphics: graphics::HookState,
+/// Every hook, in the order they're declared. `HookRegistry` installs them in this order,
+/// except where a hook has to wait for its dependencies.
+fn all() -> impl Iterator<Item = &'static Hook> {
+    system::HOOKS
+        .iter()
+        .chain(graphics::HOOKS.iter())
+        .flat_map(|hooks| hooks.iter())
 }
 
-impl HookState {
-    pub fn new() -> anyhow::Result<HookState> {
-        Ok(HookState {
-            _system: system::HookState::new()?,
-            _graphics: graphics::HookState::new()?,
-        })
+/// Waits for every thread to leave our hook handlers, which they can still be inside of
+/// for a while after the hooks have been disabled. Fails with the hooks that were still
+/// active if that takes longer than `timeout`.
+///
+/// A hook only counts as active inside `CallStats::call`, which leaves a few instructions
+/// uncounted on either side of it: the detour's entry stub and the prologue of our handler
+/// before it, and the epilogue and the return through the stub after it. Once nothing is
+/// active, we wait out `grace` as well, so that threads in that window can get out; unless
+/// one is preempted there for longer than that, it's safe to unmap our code afterwards.
+pub fn wait_until_idle(timeout: Duration, grace: Duration) -> anyhow::Result<()> {
+    let start = Instant::now();
+    loop {
+        let active: Vec<_> = all()
+            .filter(|hook| hook.stats.active() > 0)
+            .map(|hook| format!("{} ({})", hook.name, hook.stats.active()))
+            .collect();
+        if active.is_empty() {
+            std::thread::sleep(grace);
+            return Ok(());
+        }
+        if start.elapsed() >= timeout {
+            anyhow::bail!(
+                "hooks still active after {:?}: {}",
+                timeout,
+                active.join(", ")
+            );
+        }
+        std::thread::sleep(Duration::from_millis(1));
     }
 }
-singleton!(HookState);
@@ -1,5 +1,6 @@
-use crate::singleton;
+use crate::{log, singleton};
 
+use std::ops::Range;
 use std::os::raw::c_void;
 use std::ptr;
 use std::slice;
@@ -10,56 +11,183 @@ use windows::Win32::System::Memory::{VirtualProtect, PAGE_EXECUTE_READWRITE};
 struct Patch {
     address: *mut u8,
     original_bytes: Vec<u8>,
+    patched_bytes: Vec<u8>,
 }
 
-pub struct Patcher {
+impl Patch {
+    fn range(&self) -> Range<usize> {
+        let start = self.address as usize;
+        start..start + self.patched_bytes.len()
+    }
+}
+
+/// Patches that are applied and removed together, under a name.
+struct PatchGroup {
+    name: String,
     patches: Vec<Patch>,
 }
+
+pub struct Patcher {
+    groups: Vec<PatchGroup>,
+}
 singleton!(Patcher);
 
-impl Patcher {
-    pub fn new() -> anyhow::Result<Patcher> {
-        Ok(Patcher { patches: vec![] })
+unsafe fn safe_write(address: *mut u8, bytes: &[u8]) -> anyhow::Result<()> {
+    let mut old = PAGE_PROTECTION_FLAGS(0);
+    if !VirtualProtect(
+        address as *mut c_void,
+        bytes.len(),
+        PAGE_EXECUTE_READWRITE,
+        &mut old,
+    )
+    .as_bool()
+    {
+        anyhow::bail!("failed to make {:X?} writable", address);
+    }
+    ptr::copy(bytes.as_ptr(), address, bytes.len());
+    VirtualProtect(address as *mut c_void, bytes.len(), old, &mut old);
+    Ok(())
+}
+
+/// Restores the original bytes of `patches`, in reverse order, removing the patches
+/// that were restored. Patches whose bytes have been changed since we wrote them are left
+/// alone, as restoring them would clobber whoever changed them.
+unsafe fn restore(name: &str, patches: &mut Vec<Patch>) -> anyhow::Result<()> {
+    let mut errors = vec![];
+    let mut remaining = vec![];
+    while let Some(patch) = patches.pop() {
+        let current = slice::from_raw_parts(patch.address, patch.patched_bytes.len());
+        let result = if current != patch.patched_bytes.as_slice() {
+            Err(format!("{:X?} was modified after patching", patch.address))
+        } else {
+            safe_write(patch.address, &patch.original_bytes).map_err(|e| e.to_string())
+        };
+        if let Err(e) = result {
+            errors.push(e);
+            remaining.push(patch);
+        }
     }
+    remaining.reverse();
+    *patches = remaining;
 
-    pub unsafe fn safe_write(&self, addr_ptr: *mut u8, bytes: &[u8]) {
-        let mut old = PAGE_PROTECTION_FLAGS(0);
-        VirtualProtect(
-            addr_ptr as *mut c_void,
-            bytes.len(),
-            PAGE_EXECUTE_READWRITE,
-            &mut old,
-        );
-        ptr::copy(bytes.as_ptr(), addr_ptr, bytes.len());
-        VirtualProtect(addr_ptr as *mut c_void, bytes.len(), old, &mut old);
+    if !errors.is_empty() {
+        anyhow::bail!("failed to unpatch {}: {}", name, errors.join(", "));
     }
+    Ok(())
+}
+
+/// A patch group that's being built. Patches are written as they're added; if the
+/// transaction is dropped before being committed, they're all rolled back.
+pub struct PatchTransaction<'a> {
+    patcher: &'a mut Patcher,
+    group: Option<PatchGroup>,
+}
+
+impl<'a> PatchTransaction<'a> {
+    pub unsafe fn patch(&mut self, address: *mut u8, bytes: &[u8]) -> anyhow::Result<*mut u8> {
+        let group = self.group.as_mut().unwrap();
+        let range = address as usize..address as usize + bytes.len();
+        if let Some((name, _)) = self
+            .patcher
+            .groups
+            .iter()
+            .chain(std::iter::once(&*group))
+            .flat_map(|g| g.patches.iter().map(move |p| (&g.name, p)))
+            .find(|(_, p)| p.range().start < range.end && range.start < p.range().end)
+        {
+            anyhow::bail!(
+                "patch at {:X?} for {} overlaps with a patch from {}",
+                address,
+                group.name,
+                name
+            );
+        }
 
-    pub unsafe fn patch(&mut self, address: *mut u8, bytes: &[u8]) -> *mut u8 {
-        self.patches.push(Patch {
+        let patch = Patch {
             address,
             original_bytes: slice::from_raw_parts(address, bytes.len()).to_vec(),
-        });
+            patched_bytes: bytes.to_vec(),
+        };
+        safe_write(address, bytes)?;
+        group.patches.push(patch);
 
-        self.safe_write(address, bytes);
-        address
+        Ok(address)
     }
 
-    pub unsafe fn unpatch(&mut self, address: *mut u8) {
-        if let Some(index) = self.patches.iter().position(|p| p.address == address) {
-            {
-                let patch = &self.patches[index];
-                self.safe_write(patch.address, &patch.original_bytes);
+    pub fn commit(mut self) {
+        let group = self.group.take().unwrap();
+        self.patcher.groups.push(group);
+    }
+}
+
+impl<'a> Drop for PatchTransaction<'a> {
+    fn drop(&mut self) {
+        if let Some(mut group) = self.group.take() {
+            if let Err(e) = unsafe { restore(&group.name, &mut group.patches) } {
+                log!("error", "error while rolling back patches: {}", e);
             }
-            self.patches.remove(index);
         }
     }
 }
 
+impl Patcher {
+    pub fn new() -> anyhow::Result<Patcher> {
+        Ok(Patcher { groups: vec![] })
+    }
+
+    /// Starts building the patch group called `name`, which must not already exist.
+    pub fn begin(&mut self, name: &str) -> anyhow::Result<PatchTransaction<'_>> {
+        if self.is_patched(name) {
+            anyhow::bail!("patch group {} has already been applied", name);
+        }
+
+        Ok(PatchTransaction {
+            patcher: self,
+            group: Some(PatchGroup {
+                name: name.to_string(),
+                patches: vec![],
+            }),
+        })
+    }
+
+    /// Applies `patches` as the group called `name`; either all of them are applied,
+    /// or none of them are.
+    pub unsafe fn apply(&mut self, name: &str, patches: &[(*mut u8, &[u8])]) -> anyhow::Result<()> {
+        let mut transaction = self.begin(name)?;
+        for (address, bytes) in patches {
+            transaction.patch(*address, bytes)?;
+        }
+        transaction.commit();
+        Ok(())
+    }
+
+    pub fn is_patched(&self, name: &str) -> bool {
+        self.groups.iter().any(|g| g.name == name)
+    }
+
+    /// Restores the group called `name`. If any of its patches can't be restored, the
+    /// group is kept with just those patches, so that it's still reported as patched
+    /// and restoring it can be tried again.
+    pub unsafe fn unpatch(&mut self, name: &str) -> anyhow::Result<()> {
+        let index = self
+            .groups
+            .iter()
+            .position(|g| g.name == name)
+            .ok_or_else(|| anyhow::anyhow!("no patch group called {}", name))?;
+        let group = &mut self.groups[index];
+        let result = restore(&group.name, &mut group.patches);
+        if group.patches.is_empty() {
+            self.groups.remove(index);
+        }
+        result
+    }
+}
+
 impl Drop for Patcher {
     fn drop(&mut self) {
-        for patch in self.patches.iter().rev() {
-            unsafe {
-                self.safe_write(patch.address, &patch.original_bytes);
+        for group in self.groups.iter_mut().rev() {
+            if let Err(e) = unsafe { restore(&group.name, &mut group.patches) } {
+                log!("error", "error while unpatching: {}", e);
             }
         }
     }
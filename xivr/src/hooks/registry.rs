@@ -0,0 +1,358 @@
+use crate::hooks::CallStats;
+use crate::{log, signatures, singleton, util};
+
+use module::{Module, Signature};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+/// Where the function a hook attaches to comes from.
+#[derive(Clone, Copy)]
+pub enum HookSource {
+    Signature(Signature),
+    /// Entry `index` of the vtable of the class called `class_name`, found through RTTI,
+    /// or at the RVA `fallback` if the class can't be found that way.
+    Vtable {
+        class_name: &'static str,
+        fallback: usize,
+        index: usize,
+    },
+    /// Located by a function of the hook's own, which is described by the string.
+    Custom(&'static str, unsafe fn() -> anyhow::Result<*mut u8>),
+}
+
+impl HookSource {
+    unsafe fn resolve(&self) -> anyhow::Result<*mut u8> {
+        let module = util::game_module_mut()?;
+        match self {
+            HookSource::Signature(signature) => module.resolve(*signature),
+            HookSource::Vtable {
+                class_name,
+                fallback,
+                index,
+            } => {
+                let vtable = match find_vtable(module, class_name) {
+                    Ok(vtable) => vtable,
+                    Err(e) => {
+                        log!(
+                            "warn",
+                            "failed to find vtable of {} through RTTI, using fallback {:X}: {}",
+                            class_name,
+                            fallback,
+                            e
+                        );
+                        module.rel_to_abs_addr(*fallback)
+                    }
+                } as *mut usize;
+                Ok(vtable.add(*index).read() as *mut u8)
+            }
+            HookSource::Custom(_, locate) => locate(),
+        }
+    }
+}
+
+/// Finds the primary vtable of `class_name`, remembering the result, as each lookup scans
+/// the game's data sections and a class can have several of its functions hooked.
+fn find_vtable(module: &Module, class_name: &'static str) -> anyhow::Result<*mut u8> {
+    static VTABLES: Lazy<Mutex<HashMap<&'static str, Result<usize, String>>>> =
+        Lazy::new(Default::default);
+
+    let mut vtables = VTABLES.lock().unwrap();
+    let vtable = vtables.entry(class_name).or_insert_with(|| {
+        module
+            .find_vtable(class_name)
+            .map(|vtable| vtable as usize)
+            .map_err(|e| e.to_string())
+    });
+    match vtable {
+        Ok(vtable) => Ok(*vtable as *mut u8),
+        Err(e) => Err(anyhow::anyhow!("{}", e)),
+    }
+}
+
+impl fmt::Display for HookSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookSource::Signature(signature) => {
+                match signatures::ALL.iter().find(|(_, s)| s == signature) {
+                    Some((name, _)) => write!(f, "signature {}", name),
+                    None => write!(f, "signature {}", signature.pattern()),
+                }
+            }
+            HookSource::Vtable {
+                class_name, index, ..
+            } => write!(f, "vtable of {}, entry {}", class_name, index),
+            HookSource::Custom(description, _) => write!(f, "{}", description),
+        }
+    }
+}
+
+/// An installed hook, which can be switched on and off.
+pub trait HookHandle {
+    unsafe fn enable(&mut self) -> anyhow::Result<()>;
+    unsafe fn disable(&mut self) -> anyhow::Result<()>;
+}
+
+impl<T: detour::Function> HookHandle for &'static detour::StaticDetour<T> {
+    unsafe fn enable(&mut self) -> anyhow::Result<()> {
+        Ok(detour::StaticDetour::enable(*self)?)
+    }
+
+    unsafe fn disable(&mut self) -> anyhow::Result<()> {
+        Ok(detour::StaticDetour::disable(*self)?)
+    }
+}
+
+impl HookHandle for detour::RawDetour {
+    unsafe fn enable(&mut self) -> anyhow::Result<()> {
+        Ok(detour::RawDetour::enable(self)?)
+    }
+
+    unsafe fn disable(&mut self) -> anyhow::Result<()> {
+        Ok(detour::RawDetour::disable(self)?)
+    }
+}
+
+/// A hook, as declared by the module that implements it.
+pub struct Hook {
+    pub name: &'static str,
+    pub source: HookSource,
+    /// The names of hooks that have to be enabled for this one to work. They're
+    /// installed first, and disabling them disables this hook too.
+    pub dependencies: &'static [&'static str],
+    /// Hooks that aren't enabled by default are only set up, to be enabled from the
+    /// debugger when needed.
+    pub enabled_by_default: bool,
+    /// Sets the hook up on the function from `source`, without enabling it.
+    pub install: unsafe fn(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>>,
+    /// Updated by the hook's handler whenever it's called.
+    pub stats: &'static CallStats,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookStatus {
+    Installed,
+    Disabled,
+    Failed(String),
+}
+
+impl fmt::Display for HookStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookStatus::Installed => write!(f, "installed"),
+            HookStatus::Disabled => write!(f, "disabled"),
+            HookStatus::Failed(reason) => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+struct HookEntry {
+    hook: &'static Hook,
+    status: HookStatus,
+    handle: Option<Box<dyn HookHandle>>,
+}
+
+/// Whether each hook that could be installed was enabled, so that hooks toggled from the
+/// debugger stay that way across a reload.
+#[derive(Serialize, Deserialize)]
+pub struct HookRegistryState {
+    enabled: Vec<(String, bool)>,
+}
+
+/// Every hook, in the order they were installed.
+pub struct HookRegistry {
+    entries: Vec<HookEntry>,
+}
+singleton!(HookRegistry);
+
+impl HookRegistry {
+    pub fn new() -> anyhow::Result<HookRegistry> {
+        let mut registry = HookRegistry { entries: vec![] };
+
+        let mut pending: Vec<&'static Hook> = super::all().collect();
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|hook| {
+                hook.dependencies
+                    .iter()
+                    .all(|dependency| registry.entry(dependency).is_some())
+            });
+
+            match ready {
+                Some(index) => {
+                    let hook = pending.remove(index);
+                    registry.install(hook);
+                }
+                None => {
+                    // Whatever's left depends on hooks that don't exist, or on each other.
+                    for hook in pending.drain(..) {
+                        registry.fail(hook, None, "dependencies could not be installed".into());
+                    }
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+
+    fn entry(&self, name: &str) -> Option<&HookEntry> {
+        self.entries.iter().find(|e| e.hook.name == name)
+    }
+
+    fn index(&self, name: &str) -> anyhow::Result<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.hook.name == name)
+            .ok_or_else(|| anyhow::anyhow!("no hook called {}", name))
+    }
+
+    fn fail(&mut self, hook: &'static Hook, handle: Option<Box<dyn HookHandle>>, reason: String) {
+        log!("error", "failed to install hook {}: {}", hook.name, reason);
+        self.entries.push(HookEntry {
+            hook,
+            status: HookStatus::Failed(reason),
+            handle,
+        });
+    }
+
+    fn install(&mut self, hook: &'static Hook) {
+        let mut enable = hook.enabled_by_default;
+        for dependency in hook.dependencies {
+            match self.entry(dependency).map(|e| &e.status) {
+                Some(HookStatus::Installed) => {}
+                // It can be enabled later, and this hook along with it.
+                Some(HookStatus::Disabled) => enable = false,
+                _ => {
+                    return self.fail(
+                        hook,
+                        None,
+                        format!("dependency {} is not installed", dependency),
+                    )
+                }
+            }
+        }
+
+        let mut handle = match unsafe { hook.source.resolve().and_then(|t| (hook.install)(t)) } {
+            Ok(handle) => handle,
+            Err(e) => return self.fail(hook, None, e.to_string()),
+        };
+        if enable {
+            if let Err(e) = unsafe { handle.enable() } {
+                return self.fail(hook, Some(handle), e.to_string());
+            }
+        }
+
+        self.entries.push(HookEntry {
+            hook,
+            status: if enable {
+                HookStatus::Installed
+            } else {
+                HookStatus::Disabled
+            },
+            handle: Some(handle),
+        });
+    }
+
+    pub fn save_state(&self) -> HookRegistryState {
+        HookRegistryState {
+            enabled: self
+                .entries
+                .iter()
+                .filter_map(|e| match e.status {
+                    HookStatus::Installed => Some((e.hook.name.to_string(), true)),
+                    HookStatus::Disabled => Some((e.hook.name.to_string(), false)),
+                    HookStatus::Failed(_) => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Hooks are toggled in the order they were saved in, which has dependencies first.
+    /// Hooks that no longer exist, or that failed to install this time, are left alone.
+    pub fn restore_state(&mut self, state: HookRegistryState) {
+        for (name, enabled) in state.enabled {
+            let status = match self.entry(&name) {
+                Some(entry) => &entry.status,
+                None => continue,
+            };
+            let res = match (status, enabled) {
+                (HookStatus::Disabled, true) => self.enable(&name),
+                (HookStatus::Installed, false) => self.disable(&name),
+                _ => continue,
+            };
+            if let Err(e) = res {
+                log!("error", "error while restoring {} hook: {}", name, e);
+            }
+        }
+    }
+
+    pub fn hooks(&self) -> impl Iterator<Item = (&'static Hook, &HookStatus)> {
+        self.entries.iter().map(|e| (e.hook, &e.status))
+    }
+
+    /// Enables a hook that was disabled, or that was set up but failed to enable.
+    pub fn enable(&mut self, name: &str) -> anyhow::Result<()> {
+        let index = self.index(name)?;
+        let hook = self.entries[index].hook;
+        for dependency in hook.dependencies {
+            if self.entry(dependency).map(|e| &e.status) != Some(&HookStatus::Installed) {
+                anyhow::bail!("{} depends on {}, which is not enabled", name, dependency);
+            }
+        }
+
+        let entry = &mut self.entries[index];
+        let handle = entry
+            .handle
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("{} could not be installed", name))?;
+        unsafe { handle.enable()? };
+        entry.status = HookStatus::Installed;
+        Ok(())
+    }
+
+    /// Disables a hook, along with every enabled hook that depends on it.
+    pub fn disable(&mut self, name: &str) -> anyhow::Result<()> {
+        let index = self.index(name)?;
+        if self.entries[index].status != HookStatus::Installed {
+            return Ok(());
+        }
+
+        let dependents: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.hook.dependencies.contains(&name))
+            .map(|e| e.hook.name)
+            .collect();
+        for dependent in dependents {
+            self.disable(dependent)?;
+        }
+
+        let entry = &mut self.entries[index];
+        if let Some(handle) = entry.handle.as_mut() {
+            unsafe { handle.disable()? };
+        }
+        entry.status = HookStatus::Disabled;
+        Ok(())
+    }
+}
+
+impl Drop for HookRegistry {
+    fn drop(&mut self) {
+        for entry in self.entries.iter_mut().rev() {
+            if entry.status != HookStatus::Installed {
+                continue;
+            }
+            if let Some(handle) = entry.handle.as_mut() {
+                if let Err(e) = unsafe { handle.disable() } {
+                    log!(
+                        "error",
+                        "error while disabling {} hook: {}",
+                        entry.hook.name,
+                        e.to_string()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often a hook has been called and how long it took, updated from whichever threads
+/// it's called on. Time spent in the original function is tracked separately, so that
+/// what's left over is the cost of our own handler.
+///
+/// Calls that are still in progress are counted too, so that unloading can wait for every
+/// thread to leave our handlers. Hooks should go through `call` as the very first thing they
+/// do, so that the count covers as much of their code as it can.
+pub struct CallStats {
+    active: AtomicUsize,
+    calls: AtomicU64,
+    total_ns: AtomicU64,
+    original_ns: AtomicU64,
+    min_ns: AtomicU64,
+    max_ns: AtomicU64,
+}
+
+/// Times a call from `CallStats::enter` until it's dropped, during which the call counts
+/// as active.
+pub struct CallTimer {
+    stats: &'static CallStats,
+    start: Instant,
+}
+
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed().as_nanos() as u64;
+        let stats = self.stats;
+        stats.calls.fetch_add(1, Ordering::Relaxed);
+        stats.total_ns.fetch_add(elapsed, Ordering::Relaxed);
+        stats.min_ns.fetch_min(elapsed, Ordering::Relaxed);
+        stats.max_ns.fetch_max(elapsed, Ordering::Relaxed);
+        stats.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CallStatsSnapshot {
+    pub calls: u64,
+    pub total: Duration,
+    pub original: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl CallStatsSnapshot {
+    /// The time spent in our handler, outside of the original function.
+    pub fn own(&self) -> Duration {
+        self.total.saturating_sub(self.original)
+    }
+
+    /// The calls and time between `earlier` and this snapshot. Minimums and maximums
+    /// can't be split up, so they're kept as they are.
+    pub fn since(&self, earlier: &CallStatsSnapshot) -> CallStatsSnapshot {
+        CallStatsSnapshot {
+            calls: self.calls.saturating_sub(earlier.calls),
+            total: self.total.saturating_sub(earlier.total),
+            original: self.original.saturating_sub(earlier.original),
+            ..*self
+        }
+    }
+}
+
+impl CallStats {
+    pub const fn new() -> CallStats {
+        CallStats {
+            active: AtomicUsize::new(0),
+            calls: AtomicU64::new(0),
+            total_ns: AtomicU64::new(0),
+            original_ns: AtomicU64::new(0),
+            min_ns: AtomicU64::new(u64::MAX),
+            max_ns: AtomicU64::new(0),
+        }
+    }
+
+    /// Starts timing a call to the hook, which is recorded when the timer is dropped.
+    pub fn enter(&'static self) -> CallTimer {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        CallTimer {
+            stats: self,
+            start: Instant::now(),
+        }
+    }
+
+    /// Runs `handler`, the whole body of the hook, as an active call.
+    pub fn call<T>(&'static self, handler: impl FnOnce() -> T) -> T {
+        let _call = self.enter();
+        handler()
+    }
+
+    /// Calls `original`, which should call the hooked function, and counts the time it
+    /// takes as the original function's.
+    pub fn original<T>(&self, original: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let ret = original();
+        self.original_ns
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        ret
+    }
+
+    /// How many threads are currently inside the hook's handler.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    pub fn snapshot(&self) -> CallStatsSnapshot {
+        let calls = self.calls.load(Ordering::Relaxed);
+        let nanos = |value: &AtomicU64| Duration::from_nanos(value.load(Ordering::Relaxed));
+        CallStatsSnapshot {
+            calls,
+            total: nanos(&self.total_ns),
+            original: nanos(&self.original_ns),
+            min: if calls == 0 {
+                Duration::ZERO
+            } else {
+                nanos(&self.min_ns)
+            },
+            max: nanos(&self.max_ns),
+        }
+    }
+
+    pub fn reset(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+        self.total_ns.store(0, Ordering::Relaxed);
+        self.original_ns.store(0, Ordering::Relaxed);
+        self.min_ns.store(u64::MAX, Ordering::Relaxed);
+        self.max_ns.store(0, Ordering::Relaxed);
+    }
+}
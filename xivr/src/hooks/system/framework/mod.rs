@@ -1,4 +1,5 @@
-use crate::log;
+use crate::game::offsets::classes::system::framework::Framework;
+use crate::hooks::{CallStats, Hook, HookHandle, HookSource};
 use crate::util;
 
 use detour::static_detour;
@@ -8,66 +9,64 @@ static_detour! {
     pub static Framework_Tick_Detour: fn(usize) -> usize;
 }
 
-pub struct HookState;
-impl Drop for HookState {
-    fn drop(&mut self) {
-        let res = unsafe { Framework_Tick_Detour.disable() };
-        if let Err(e) = res {
-            log!(
-                "error",
-                "error while disabling game detour: {}",
-                e.to_string()
-            )
-        }
-    }
-}
+static TICK_STATS: CallStats = CallStats::new();
+
+pub static HOOKS: &[Hook] = &[Hook {
+    name: "Framework::Tick",
+    source: HookSource::Vtable {
+        class_name: "Client::System::Framework::Framework",
+        fallback: Framework::VTBLS[0] as usize,
+        index: Framework::vfuncs::Tick,
+    },
+    dependencies: &[],
+    enabled_by_default: true,
+    install,
+    stats: &TICK_STATS,
+}];
 
-pub unsafe fn install() -> anyhow::Result<HookState> {
-    use crate::game::offsets::classes::system::framework::Framework;
-    let module = util::game_module_mut()?;
-    let framework_vtbl = module.rel_to_abs_addr(Framework::VTBLS[0] as usize) as *mut usize;
-    let framework_tick_ptr = framework_vtbl.add(Framework::vfuncs::Tick).read();
-    let framework_tick: fn(usize) -> usize = mem::transmute(framework_tick_ptr);
+unsafe fn install(target: *mut u8) -> anyhow::Result<Box<dyn HookHandle>> {
+    let framework_tick: fn(usize) -> usize = mem::transmute(target);
 
     Framework_Tick_Detour.initialize(framework_tick, |f| {
-        util::handle_error_in_block(|| {
-            use crate::debugger::Debugger;
-            use crate::xr::XR;
+        TICK_STATS.call(|| {
+            util::handle_error_in_block(|| {
+                use crate::debugger::Debugger;
+                use crate::xr::XR;
 
-            if let Some(debugger) = Debugger::get_mut() {
-                debugger.pre_update()?;
-            }
+                if let Some(debugger) = Debugger::get_mut() {
+                    debugger.pre_update()?;
+                }
 
-            if let Some(xr) = XR::get_mut() {
-                xr.pre_update()?;
-            }
+                if let Some(xr) = XR::get_mut() {
+                    xr.pre_update()?;
+                }
 
-            let ret = Framework_Tick_Detour.call(f);
+                let ret = TICK_STATS.original(|| Framework_Tick_Detour.call(f));
 
-            if let Some(xr) = XR::get_mut() {
-                xr.post_update()?;
-            }
+                if let Some(xr) = XR::get_mut() {
+                    xr.post_update()?;
+                }
 
-            if crate::tier2_loadable() {
-                let load_result = crate::load_tier2();
-                if let Err(err) = load_result {
-                    crate::load_fail(err.to_string());
+                if crate::tier2_loadable() {
+                    let load_result = crate::load_tier2();
+                    if let Err(err) = load_result {
+                        crate::load_fail(err.to_string());
+                    }
                 }
-            }
 
-            // yolo
-            if cfg!(not(feature = "dalamud")) {
-                use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_F7};
+                // yolo
+                if cfg!(not(feature = "dalamud")) {
+                    use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_F7};
 
-                if (GetAsyncKeyState(VK_F7.0.into()) & 0x01) != 0 {
-                    crate::xivr_unload();
+                    if (GetAsyncKeyState(VK_F7.0.into()) & 0x01) != 0 {
+                        crate::xivr_unload();
+                    }
                 }
-            }
 
-            Ok(ret)
+                Ok(ret)
+            })
         })
     })?;
-    Framework_Tick_Detour.enable()?;
 
-    Ok(HookState {})
+    Ok(Box::new(&Framework_Tick_Detour))
 }
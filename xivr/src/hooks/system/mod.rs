@@ -1,13 +1,5 @@
 pub mod framework;
 
-pub struct HookState {
-    _framework: framework::HookState,
-}
+use crate::hooks::Hook;
 
-impl HookState {
-    pub fn new() -> anyhow::Result<HookState> {
-        Ok(HookState {
-            _framework: unsafe { framework::install()? },
-        })
-    }
-}
+pub static HOOKS: &[&[Hook]] = &[framework::HOOKS];
@@ -6,13 +6,14 @@ mod hooks;
 #[macro_use]
 mod log;
 mod debugger;
-mod module;
 mod xr;
 #[macro_use]
 mod util;
 mod ct_config;
+mod reload;
+mod signatures;
 
-use hooks::HookState;
+use hooks::HookRegistry;
 use log::Logger;
 use module::Module;
 
@@ -91,6 +92,40 @@ unsafe fn patch_symbol_search_path() -> Result<()> {
     Ok(())
 }
 
+// Logs where each signature that can't be resolved might have moved to, to help with
+// fixing them after a game patch.
+fn log_signature_recovery(module: &mut Module) {
+    for (name, signature) in signatures::ALL {
+        let error = match module.resolve(*signature) {
+            Ok(_) => continue,
+            Err(error) => error,
+        };
+        log!("signatures", "failed to resolve {}: {}", name, error);
+
+        // Not being able to look for candidates is no reason to stop loading.
+        let candidates = match module.recover_signature(
+            *signature,
+            ct_config::hooks::SIGNATURE_RECOVERY_MAX_DISTANCE,
+        ) {
+            Ok(candidates) => candidates,
+            Err(error) => {
+                log!("signatures", "  failed to look for candidates: {}", error);
+                continue;
+            }
+        };
+        for candidate in candidates {
+            log!(
+                "signatures",
+                "  candidate {:X} ({:.0}% similar, {} matches): {}",
+                candidate.offset,
+                candidate.similarity * 100.0,
+                candidate.matches,
+                candidate.pattern
+            );
+        }
+    }
+}
+
 unsafe fn load_tier1(parameters: Option<&LoadParameters>) -> Result<()> {
     log!("tier1", "start");
     let mut modules = Module::get_all();
@@ -99,7 +134,10 @@ unsafe fn load_tier1(parameters: Option<&LoadParameters>) -> Result<()> {
         .find(|x| x.filename().as_deref() == Some("ffxiv_dx11.exe"))
         .ok_or_else(|| Error::msg("failed to find ff14 module"))?;
     ffxiv_module.backup_image();
-    ffxiv_module.load_cache()?;
+    ffxiv_module.set_strict(ct_config::hooks::STRICT_SIGNATURES);
+    ffxiv_module.load_cache(&util::this_module_directory()?.join("cache"))?;
+    ffxiv_module.scan_batch(signatures::ALL.iter().map(|(_, signature)| *signature))?;
+    log_signature_recovery(ffxiv_module);
 
     util::set_game_module(ffxiv_module.clone())?;
     log!("tier1", "located module");
@@ -109,9 +147,19 @@ unsafe fn load_tier1(parameters: Option<&LoadParameters>) -> Result<()> {
 
     hooks::Patcher::create()?;
     debugger::Debugger::create()?;
-    HookState::create()?;
+    HookRegistry::create()?;
     log!("tier1", "installed hooks");
 
+    if let Some(state) = reload::take()? {
+        if let (Some(debugger), Some(state)) = (debugger::Debugger::get_mut(), state.debugger) {
+            debugger.restore_state(state);
+        }
+        if let (Some(registry), Some(state)) = (HookRegistry::get_mut(), state.hooks) {
+            registry.restore_state(state);
+        }
+        log!("tier1", "restored state from previous instance");
+    }
+
     util::game_module_mut()?.save_cache()?;
 
     if let Some(parameters) = parameters {
@@ -172,7 +220,17 @@ unsafe fn xivr_load_impl(parameters: *const LoadParameters) -> Result<()> {
             (None, None) => log!("panic", "Panic! something at somewhere"),
         };
 
-        log!("panic", "{:?}", backtrace::Backtrace::new_unresolved());
+        let backtrace = backtrace::Backtrace::new();
+        let mut symbolizer = util::symbols::Symbolizer::new();
+        for frame in symbolizer.resolve(backtrace.frames().iter()) {
+            log!(
+                "panic",
+                "  {}+0x{:X} {}",
+                frame.module,
+                frame.offset,
+                frame.symbol.unwrap_or_default()
+            );
+        }
     }));
 
     let r = std::panic::catch_unwind(|| load_tier1(parameters));
@@ -198,12 +256,41 @@ pub unsafe extern "system" fn xivr_load(parameters: *const LoadParameters) -> bo
 #[allow(clippy::missing_safety_doc)]
 pub unsafe extern "system" fn xivr_unload() {
     log!("xivr", "unloading!");
-    HookState::destroy();
+    // Which hooks were enabled has to be taken before they're all removed.
+    let hook_state = HookRegistry::get().map(|r| r.save_state());
+    HookRegistry::destroy();
     hooks::Patcher::destroy();
 
-    std::thread::spawn(|| {
+    std::thread::spawn(move || {
+        use std::time::Duration;
         use windows::Win32::System::LibraryLoader::FreeLibraryAndExitThread;
-        std::thread::sleep(std::time::Duration::from_millis(100)); // bodge
+
+        // The hooks are disabled, but threads may still be inside them, and they'll need
+        // everything we're about to destroy (and our code) until they leave.
+        let timeout = Duration::from_millis(ct_config::hooks::UNLOAD_TIMEOUT_MS);
+        let grace = Duration::from_millis(ct_config::hooks::UNLOAD_GRACE_PERIOD_MS);
+        let idle = hooks::wait_until_idle(timeout, grace);
+
+        // Signatures found since loading are kept for the next instance, and so is the
+        // rest of our state, which it picks up when it's loaded. This happens even if we
+        // can't unload, so that a new instance can still pick up where we left off.
+        let state = reload::ReloadState::new(
+            debugger::Debugger::get().map(|d| d.save_state()),
+            hook_state,
+        );
+        util::handle_error(util::game_module_mut().and_then(|m| m.save_cache()));
+        util::handle_error(reload::save(&state));
+
+        if let Err(e) = idle {
+            log!(
+                "xivr",
+                "not unloading, as {}; hooks and patches are removed, but XR and the debugger \
+                 are still alive for the threads inside of them, and the module stays loaded",
+                e
+            );
+            return;
+        }
+        hooks::jump_table::free_retired_trampolines();
 
         xr::XR::destroy();
         debugger::Debugger::destroy();
@@ -0,0 +1,81 @@
+use crate::debugger::DebuggerState;
+use crate::hooks::HookRegistryState;
+use crate::util;
+
+use serde::{Deserialize, Serialize};
+
+use std::path::PathBuf;
+
+use windows::Win32::System::Threading::GetCurrentProcessId;
+
+// Bump this whenever the state changes shape, so that an older module's state is ignored
+// instead of being misread.
+const RELOAD_STATE_VERSION: u32 = 2;
+
+/// What an unloading instance of the module hands over to the next one to be loaded into
+/// the same process. XR isn't handed over: it's torn down before the state is written, and
+/// the next instance starts a new session.
+///
+/// Neither is the debugger's capture, which lives in memory and holds pointers into the
+/// old instance, so the stream and command selected in it and the selected render target
+/// creation are gone too. A capture that should survive a reload can be saved to a file
+/// and loaded again from the command stream tab.
+#[derive(Serialize, Deserialize)]
+pub struct ReloadState {
+    version: u32,
+    process_id: u32,
+    pub debugger: Option<DebuggerState>,
+    pub hooks: Option<HookRegistryState>,
+}
+
+impl ReloadState {
+    pub fn new(debugger: Option<DebuggerState>, hooks: Option<HookRegistryState>) -> ReloadState {
+        ReloadState {
+            version: RELOAD_STATE_VERSION,
+            process_id: unsafe { GetCurrentProcessId() },
+            debugger,
+            hooks,
+        }
+    }
+}
+
+fn path() -> anyhow::Result<PathBuf> {
+    Ok(util::this_module_directory()?.join("reload_state.json"))
+}
+
+pub fn save(state: &ReloadState) -> anyhow::Result<()> {
+    let path = path()?;
+    std::fs::write(&path, serde_json::to_string(state)?)?;
+    log!("reload", "saved state to {}", path.display());
+    Ok(())
+}
+
+/// Takes the state left behind by the previous instance, if it was in this process and
+/// is of the current version. The state is removed either way, so it's only used once.
+pub fn take() -> anyhow::Result<Option<ReloadState>> {
+    let path = path()?;
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    std::fs::remove_file(&path)?;
+
+    let state: ReloadState = match serde_json::from_str(&contents) {
+        Ok(state) => state,
+        Err(e) => {
+            log!("reload", "ignoring unreadable state: {}", e);
+            return Ok(None);
+        }
+    };
+    if state.version != RELOAD_STATE_VERSION {
+        log!("reload", "ignoring state of version {}", state.version);
+        return Ok(None);
+    }
+    if state.process_id != unsafe { GetCurrentProcessId() } {
+        log!("reload", "ignoring state from another process");
+        return Ok(None);
+    }
+
+    Ok(Some(state))
+}
@@ -0,0 +1,24 @@
+//! Every signature used to locate game code. These are kept in one place, independent
+//! of the rest of the crate, so that they can be validated against an executable on disk
+//! without having to load the game (see `sigcheck`).
+use module::Signature;
+
+macro_rules! signatures {
+    ($($name:ident: $signature:expr;)*) => {
+        $(pub const $name: Signature = $signature;)*
+
+        pub const ALL: &[(&str, Signature)] = &[$((stringify!($name), $name),)*];
+    };
+}
+
+signatures! {
+    CONSTANT_BUFFER_LOAD_BUFFER: Signature::Function("4C 89 44 24 ? 56 57 41 57");
+    CONTEXT_ALLOC: Signature::Function("4C 8D 4A 0F 4C 8B C1");
+    CONTEXT_PUSH_BACK_COMMAND: Signature::Function("83 41 30 FF");
+    IMMEDIATE_CONTEXT_PROCESS_COMMANDS: Signature::RelativeCallsite("E8 ? ? ? ? 48 8B 4B 30 FF 15 ? ? ? ?");
+    SWAPCHAIN_PRESENT: Signature::RelativeCallsite("E8 ? ? ? ? C6 83 ? ? ? ? ? 48 8B 4B 70");
+    CAMERA_UPDATE_CONSTANT_BUFFERS: Signature::RelativeCallsite("E8 ? ? ? ? E9 ? ? ? ? 42 83 64 37 ? ?");
+    RENDER_MANAGER_RENDER: Signature::Function("40 53 55 57 41 56 41 57 48 83 EC 60");
+    RENDER_MANAGER_RENDER_UI: Signature::Function("48 89 5C 24 ? 48 89 6C 24 ? 56 57 41 54 41 56 41 57 48 83 EC 40 44 8B 05 ? ? ? ?");
+    GET_SOME_GLOBAL_STRUCT: Signature::RelativeCallsite("E8 ? ? ? ? 48 8B 58 <offset:u8>");
+}
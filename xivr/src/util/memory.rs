@@ -0,0 +1,21 @@
+use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+/// Reads a `T` from `address` without faulting if it's unmapped or inaccessible, for
+/// following pointers that may not be valid (e.g. those typed in by the user).
+pub fn read_memory<T: Copy>(address: *const T) -> Option<T> {
+    let mut value = std::mem::MaybeUninit::<T>::uninit();
+    let mut bytes_read = 0usize;
+    let success = unsafe {
+        ReadProcessMemory(
+            GetCurrentProcess(),
+            address as *const _,
+            value.as_mut_ptr() as *mut _,
+            std::mem::size_of::<T>(),
+            &mut bytes_read,
+        )
+    };
+
+    (success.as_bool() && bytes_read == std::mem::size_of::<T>())
+        .then(|| unsafe { value.assume_init() })
+}
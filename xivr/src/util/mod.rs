@@ -1,7 +1,10 @@
 mod error_handling;
+mod memory;
 mod modules;
 mod singleton;
+pub mod symbols;
 
 pub use error_handling::*;
+pub use memory::*;
 pub use modules::*;
 pub use singleton::*;
@@ -1,5 +1,5 @@
-use crate::module::Module;
 use anyhow::anyhow;
+use module::Module;
 use once_cell::unsync::OnceCell;
 
 static mut GAME_MODULE: OnceCell<Module> = OnceCell::new();
@@ -0,0 +1,141 @@
+use super::game_module_mut;
+use crate::game::offsets::symbols;
+
+use module::Module;
+use once_cell::sync::OnceCell;
+use xivr_capture::Frame;
+
+use std::collections::HashMap;
+use std::os::raw::c_void;
+
+static SYMBOLS: OnceCell<Vec<(usize, &'static str)>> = OnceCell::new();
+
+/// The functions named in data.yml, sorted by their offset in the game module. Virtual
+/// functions are found by reading the game's vtables, so this is built on first use.
+fn game_symbols() -> anyhow::Result<&'static [(usize, &'static str)]> {
+    SYMBOLS
+        .get_or_try_init(|| {
+            let game_module = game_module_mut()?;
+            let mut symbols: Vec<_> = symbols::FUNCTIONS.to_vec();
+            symbols.extend(symbols::VFUNCS.iter().map(|(vtbl, index, name)| {
+                let entry =
+                    game_module.rel_to_abs_addr(vtbl + index * std::mem::size_of::<usize>());
+                let function = unsafe { *(entry as *const *const u8) };
+                (game_module.abs_to_rel_addr(function) as usize, *name)
+            }));
+            symbols.sort_by_key(|(offset, _)| *offset);
+            symbols.dedup_by_key(|(offset, _)| *offset);
+            Ok(symbols)
+        })
+        .map(|s| s.as_slice())
+}
+
+/// Names `offset` into the game module as `Function+0xOffset`. The function is found
+/// through the module's unwind data where possible; otherwise, the closest named function
+/// before `offset` is used.
+pub fn symbolize(offset: usize) -> anyhow::Result<Option<String>> {
+    let symbols = game_symbols()?;
+    let start = match game_module_mut()?.function_bounds(offset)? {
+        Some(bounds) => symbols
+            .binary_search_by_key(&bounds.start, |(offset, _)| *offset)
+            .ok(),
+        None => symbols
+            .partition_point(|(start, _)| *start <= offset)
+            .checked_sub(1),
+    };
+    Ok(start.map(|i| {
+        let (start, name) = symbols[i];
+        format!("{}+0x{:X}", name, offset - start)
+    }))
+}
+
+/// Names the function containing `ip`, which is in the module based at `mba`. Addresses in
+/// the game are named with data.yml; addresses elsewhere use whatever debug symbols are
+/// available, which can be slow to look up.
+pub fn symbolize_address(ip: *mut c_void, mba: *mut c_void) -> Option<String> {
+    let in_game = game_module_mut().map_or(false, |m| m.base == mba as *mut u8);
+    if in_game {
+        return symbolize((ip as usize).wrapping_sub(mba as usize))
+            .ok()
+            .flatten();
+    }
+
+    let mut symbolized = None;
+    backtrace::resolve(ip, |symbol| {
+        if symbolized.is_some() {
+            return;
+        }
+        symbolized = symbol.name().map(|name| match symbol.addr() {
+            Some(addr) => format!("{}+0x{:X}", name, (ip as usize).wrapping_sub(addr as usize)),
+            None => name.to_string(),
+        });
+    });
+    symbolized
+}
+
+/// Names the function `frame` is in, which doesn't need to have been resolved.
+pub fn symbolize_frame(frame: &backtrace::BacktraceFrame) -> Option<String> {
+    let mba = frame.module_base_address().unwrap_or(std::ptr::null_mut());
+    symbolize_address(frame.ip(), mba)
+}
+
+/// Names frames and the modules they're in, looking up each address only once. Modules
+/// are named as they were when the symbolizer was created.
+pub struct Symbolizer {
+    module_names: HashMap<*const u8, String>,
+    symbols: HashMap<usize, Option<String>>,
+}
+
+impl Symbolizer {
+    pub fn new() -> Symbolizer {
+        Symbolizer {
+            module_names: module_names(),
+            symbols: HashMap::new(),
+        }
+    }
+
+    pub fn module_name(&self, mba: *const u8) -> String {
+        self.module_names
+            .get(&mba)
+            .cloned()
+            .unwrap_or_else(|| format!("{:X?}", mba))
+    }
+
+    pub fn symbol(&mut self, frame: &backtrace::BacktraceFrame) -> Option<String> {
+        self.symbols
+            .entry(frame.ip() as usize)
+            .or_insert_with(|| symbolize_frame(frame))
+            .clone()
+    }
+
+    /// Names `frames`. Callstacks have most of their frames in common, so each address is
+    /// only looked up the first time it's seen.
+    pub fn resolve<'a>(
+        &mut self,
+        frames: impl Iterator<Item = &'a backtrace::BacktraceFrame>,
+    ) -> Vec<Frame> {
+        frames
+            .map(|frame| {
+                let mba = frame.module_base_address().unwrap_or(std::ptr::null_mut());
+                Frame {
+                    module: self.module_name(mba as *const u8),
+                    offset: (frame.ip() as usize).wrapping_sub(mba as usize),
+                    symbol: self.symbol(frame),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The names of the loaded modules, keyed on their base address.
+fn module_names() -> HashMap<*const u8, String> {
+    Module::get_all()
+        .iter()
+        .map(|m| {
+            (
+                m.base as *const u8,
+                m.filename().unwrap_or_else(|| "unknown".to_string()),
+            )
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 use super::framebuffer;
 use crate::game::graphics::kernel;
+use crate::signatures;
 
 use windows::Win32::Graphics::Direct3D11 as d3d;
 use windows::Win32::Graphics::Dxgi as dxgi;
@@ -49,6 +50,7 @@ pub(crate) struct FramebufferBlitter {
     rasterizer_state: d3d::ID3D11RasterizerState,
     depth_stencil_state: d3d::ID3D11DepthStencilState,
     some_global_struct: *const u8,
+    some_global_struct_offset: usize,
 }
 impl FramebufferBlitter {
     pub fn new(device: d3d::ID3D11Device) -> anyhow::Result<FramebufferBlitter> {
@@ -221,12 +223,15 @@ impl FramebufferBlitter {
             })?
         };
 
+        let module = crate::util::game_module_mut()?;
         let some_global_struct = unsafe {
-            let module = crate::util::game_module_mut()?;
             let mystery_function: fn() -> *const u8 =
-                std::mem::transmute(module.scan_for_relative_callsite("E8 ? ? ? ? 48 8B 58 60")?);
+                std::mem::transmute(module.resolve(signatures::GET_SOME_GLOBAL_STRUCT)?);
             mystery_function()
         };
+        let some_global_struct_offset = module
+            .resolve_captures(signatures::GET_SOME_GLOBAL_STRUCT)?
+            .get("offset")?;
 
         Ok(FramebufferBlitter {
             screen_draw_vertex,
@@ -239,6 +244,7 @@ impl FramebufferBlitter {
             rasterizer_state,
             depth_stencil_state,
             some_global_struct,
+            some_global_struct_offset,
         })
     }
 
@@ -273,7 +279,8 @@ impl FramebufferBlitter {
         dc.PSSetShader(&self.screen_draw_pixel, std::ptr::null_mut(), 0);
         {
             let texture: &kernel::Texture = {
-                let some_struct = *(self.some_global_struct.add(0x60) as *const *const u8);
+                let some_struct = *(self.some_global_struct.add(self.some_global_struct_offset)
+                    as *const *const u8);
                 &**(some_struct.add(0x10) as *const *const kernel::Texture)
             };
             let srv = texture.shader_resource_view().clone().map(|x| x.into());